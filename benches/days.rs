@@ -0,0 +1,98 @@
+use advent_of_code_2021::input;
+use advent_of_code_2021::registry::{self, Puzzle, PUZZLES};
+use std::collections::HashMap;
+use std::process::exit;
+
+const USAGE: &str = "usage: cargo bench --bench days -- [day|all] \
+                     [--iters N] [--input DAY=PATH]... [--out PATH]";
+
+struct Args {
+    puzzles: Vec<&'static Puzzle>,
+    iters: u32,
+    inputs: HashMap<u32, String>,
+    out: Option<String>,
+}
+
+fn parse_input(arg: &str) -> Option<(u32, String)> {
+    let (day, path) = arg.split_once('=')?;
+    Some((day.parse().ok()?, path.to_string()))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let (mut puzzles, mut iters) = (PUZZLES.iter().collect(), 10);
+    let (mut inputs, mut out) = (HashMap::new(), None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // passed by cargo bench to every bench target
+            "--bench" => {}
+            "--iters" => {
+                let n = args.next().ok_or("missing iteration count")?;
+                iters = n.parse().map_err(|_| format!("bad iters: {}", n))?;
+            }
+            "--input" => {
+                let arg = args.next().ok_or("missing DAY=PATH")?;
+                let (day, path) =
+                    parse_input(&arg).ok_or(format!("bad input: {}", arg))?;
+                inputs.insert(day, path);
+            }
+            "--out" => out = Some(args.next().ok_or("missing out path")?),
+            "all" => puzzles = PUZZLES.iter().collect(),
+            day => {
+                let day = day.parse().map_err(|_| USAGE.to_string())?;
+                let puzzle = registry::get(day);
+                puzzles =
+                    vec![puzzle.ok_or(format!("no solution for {}", day))?];
+            }
+        }
+    }
+    Ok(Args { puzzles, iters, inputs, out })
+}
+
+fn main() {
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(2);
+    });
+    let mut summary = String::from("day\tparse_ns\tpart1_ns\tpart2_ns\n");
+    let mut ok = true;
+    for puzzle in args.puzzles {
+        let path = args.inputs.get(&puzzle.day).cloned();
+        let path = path.unwrap_or_else(|| input::default_path(puzzle.day));
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("day{}: skipping: {}: {}", puzzle.day, path, err);
+                continue;
+            }
+        };
+        let t = match (puzzle.time)(&text, args.iters) {
+            Ok(t) => t,
+            Err(err) => {
+                eprintln!("day{}: {}: {}", puzzle.day, path, err);
+                ok = false;
+                continue;
+            }
+        };
+        eprintln!(
+            "day{:<2}  parse {:>10.2?}  part1 {:>10.2?}  part2 {:>10.2?}",
+            puzzle.day, t.parse, t.part1, t.part2
+        );
+        summary += &format!(
+            "{}\t{}\t{}\t{}\n",
+            puzzle.day,
+            t.parse.as_nanos(),
+            t.part1.as_nanos(),
+            t.part2.as_nanos()
+        );
+    }
+    match args.out {
+        Some(path) => std::fs::write(&path, summary).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            exit(1);
+        }),
+        None => print!("{}", summary),
+    }
+    if !ok {
+        exit(1);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::solution::{self, Solution};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+fn sample(iters: u32, mut f: impl FnMut()) -> Duration {
+    let samples = (0..iters.max(1)).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed()
+    });
+    median(samples.collect())
+}
+
+// fails without timing anything if either part does
+pub fn time<S: Solution>(
+    s: &str,
+    iters: u32,
+) -> Result<Timings, solution::Error> {
+    let input = S::parse(s)?;
+    S::part1(&input)?;
+    S::part2(&input)?;
+    let parse = sample(iters, || {
+        black_box(S::parse(black_box(s)).is_ok());
+    });
+    let part1 = sample(iters, || {
+        black_box(S::part1(black_box(&input)).is_ok());
+    });
+    let part2 = sample(iters, || {
+        black_box(S::part2(black_box(&input)).is_ok());
+    });
+    Ok(Timings { parse, part1, part2 })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day1::Day1;
+    use crate::day16::Day16;
+
+    #[test]
+    fn test_time() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        assert!(time::<Day1>(input, 3).is_ok());
+        assert!(time::<Day1>("x", 3).is_err());
+        assert!(time::<Day16>("0C0000", 3).is_err());
+        let samples = [3, 1, 2].map(Duration::from_nanos).to_vec();
+        assert_eq!(Duration::from_nanos(2), median(samples));
+    }
+}
@@ -0,0 +1,144 @@
+use advent_of_code_2021::input;
+use advent_of_code_2021::registry::{self, Puzzle, PUZZLES};
+use advent_of_code_2021::verify::{self, Outcome};
+use std::process::exit;
+
+const USAGE: &str = "usage: aoc run <day|all> [--part <1|2>] [input_path]
+       aoc verify <day|all> [--part <1|2>]";
+
+#[derive(PartialEq)]
+enum Cmd {
+    Run,
+    Verify,
+}
+
+struct Args {
+    cmd: Cmd,
+    puzzles: Vec<&'static Puzzle>,
+    parts: Vec<u32>,
+    path: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let cmd = match args.next().as_deref() {
+        Some("run") => Cmd::Run,
+        Some("verify") => Cmd::Verify,
+        _ => return Err(USAGE.to_string()),
+    };
+    let puzzles = match args.next().as_deref() {
+        Some("all") => PUZZLES.iter().collect(),
+        Some(day) => {
+            let day = day.parse().map_err(|_| format!("bad day: {}", day))?;
+            let puzzle = registry::get(day);
+            vec![puzzle.ok_or(format!("no solution for day {}", day))?]
+        }
+        None => return Err(USAGE.to_string()),
+    };
+    let (mut parts, mut path) = (vec![1, 2], None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or("missing part")?;
+                match part.as_str() {
+                    "1" => parts = vec![1],
+                    "2" => parts = vec![2],
+                    _ => return Err(format!("bad part: {}", part)),
+                }
+            }
+            _ if cmd == Cmd::Run && path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    if path.is_some() && puzzles.len() > 1 {
+        return Err("input path can't be given with all".to_string());
+    }
+    Ok(Args { cmd, puzzles, parts, path })
+}
+
+fn run(puzzle: &Puzzle, parts: &[u32], path: &str, label: bool) -> bool {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("day{}: {}: {}", puzzle.day, path, err);
+            return false;
+        }
+    };
+    for &part in parts {
+        let answer = match (puzzle.part(part).unwrap())(&text) {
+            Ok(answer) => answer,
+            Err(err) => {
+                eprintln!("day{}: {}: {}", puzzle.day, path, err);
+                return false;
+            }
+        };
+        if label {
+            println!("day{} part{}: {}", puzzle.day, part, answer);
+        } else {
+            println!("{}", answer);
+        }
+    }
+    true
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn verify(puzzle: &Puzzle, parts: &[u32], tally: &mut Tally) {
+    let path = input::default_path(puzzle.day);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => {
+            println!("day{}: missing input: {}: {}", puzzle.day, path, err);
+            tally.missing += parts.len();
+            return;
+        }
+    };
+    let answers = std::fs::read_to_string(input::answers_path(puzzle.day));
+    for &part in parts {
+        let check = verify::check(puzzle, part, &text, answers.as_deref().ok());
+        match check.outcome {
+            Outcome::Pass => tally.passed += 1,
+            Outcome::Fail(_) => tally.failed += 1,
+            Outcome::Missing => tally.missing += 1,
+        }
+        println!(
+            "day{} part{}: {} ({:.2?})",
+            puzzle.day, part, check.outcome, check.elapsed
+        );
+    }
+}
+
+fn main() {
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(2);
+    });
+    if args.cmd == Cmd::Verify {
+        let mut tally = Tally::default();
+        for puzzle in args.puzzles {
+            verify(puzzle, &args.parts, &mut tally);
+        }
+        println!(
+            "{} passed, {} failed, {} missing",
+            tally.passed, tally.failed, tally.missing
+        );
+        if tally.failed > 0 {
+            exit(1);
+        }
+        return;
+    }
+    let label = args.puzzles.len() > 1;
+    let mut ok = true;
+    for puzzle in args.puzzles {
+        let path = args.path.clone();
+        let path = path.unwrap_or_else(|| input::default_path(puzzle.day));
+        ok &= run(puzzle, &args.parts, &path, label);
+    }
+    if !ok {
+        exit(1);
+    }
+}
@@ -0,0 +1,19 @@
+use advent_of_code_2021::{day24, input};
+use std::process::exit;
+
+fn main() {
+    let prog = input::or_exit(day24::parse(&input::read_input()));
+    for largest in [true, false] {
+        match day24::model_number(&prog, largest) {
+            Some(n) => println!("{}", n),
+            None => {
+                eprintln!(
+                    "{}: {}",
+                    input::path_from_args(),
+                    day24::NO_MODEL_NUMBER
+                );
+                exit(1)
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use advent_of_code_2021::day25::{self, Floor};
+use advent_of_code_2021::input;
+
+fn main() {
+    let grid = input::or_exit(day25::parse(&input::read_input()));
+    let dump = std::env::args().any(|arg| arg == "--dump");
+    let steps = day25::settle(&grid, |i, grid| {
+        if dump {
+            println!("after step {}:\n{}", i, Floor(grid));
+        }
+    });
+    println!("{}", steps);
+}
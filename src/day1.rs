@@ -0,0 +1,58 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+pub fn parse(s: &str) -> Result<Vec<i32>> {
+    let src = Source::new(s);
+    s.lines().map(|line| src.parse(line, "integer")).collect()
+}
+
+pub fn part1(nums: &[i32]) -> usize {
+    nums.iter().zip(nums.iter().skip(1)).filter(|(a, b)| a < b).count()
+}
+
+pub fn part2(nums: &[i32]) -> usize {
+    (0..nums.len() - 3)
+        .filter(|&i| {
+            let w1 = &nums[i..i + 3];
+            let w2 = &nums[i + 1..i + 4];
+            w1.iter().sum::<i32>() < w2.iter().sum()
+        })
+        .count()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(nums: &Vec<i32>) -> Solved {
+        Ok(part1(nums).into())
+    }
+
+    fn part2(nums: &Vec<i32>) -> Solved {
+        Ok(part2(nums).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let increases = part1(input);
+        assert_eq!(7, increases);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let increases = part2(input);
+        assert_eq!(5, increases);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::grid;
+use crate::parse::Result;
+use crate::solution::{self, Solution, Solved};
+
+pub type Grid = grid::Grid<u8>;
+
+fn step(grid: &mut Grid) -> usize {
+    let mut steps = 0;
+    let mut q = Vec::from_iter(grid.pts());
+    while let Some(pt) = q.pop() {
+        grid[pt] += 1;
+        if grid[pt] == 10 {
+            q.extend(grid.nbrs8(pt));
+            steps += 1;
+        }
+    }
+    grid.values_mut().filter(|val| **val > 9).for_each(|val| *val = 0);
+    steps
+}
+
+pub fn part1(mut grid: Grid) -> usize {
+    (0..100).map(|_| step(&mut grid)).sum()
+}
+
+pub fn part2(mut grid: Grid) -> usize {
+    (1..).find(|_| step(&mut grid) == grid.len()).unwrap()
+}
+
+pub fn parse(s: &str) -> Result<Grid> {
+    Grid::parse_digits(s)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Grid;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(grid: &Grid) -> Solved {
+        Ok(part1(grid.clone()).into())
+    }
+
+    fn part2(grid: &Grid) -> Solved {
+        Ok(part2(grid.clone()).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1656, part1(parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(195, part2(parse(INPUT).unwrap()));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::parse::{Result, Source};
+use crate::pt::Pt;
+use crate::solution::{self, Solution, Solved};
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: i32,
+    height: i32,
+    pub dots: HashSet<Pt>,
+}
+
+impl Grid {
+    fn flip(&mut self, pt: Pt, fold: Pt) {
+        let flipped = if fold.0 > 0 {
+            let d = pt.0 - fold.0;
+            (fold.0 - d, pt.1)
+        } else {
+            let d = pt.1 - fold.1;
+            (pt.0, fold.1 - d)
+        };
+        self.dots.insert(flipped);
+        self.dots.remove(&pt);
+    }
+
+    pub fn fold(&mut self, fold: Pt) {
+        let should_flip =
+            |(x, y)| fold.0 > 0 && x > fold.0 || fold.1 > 0 && y > fold.1;
+        let to_flip: Vec<Pt> =
+            self.dots.iter().filter(|&&pt| should_flip(pt)).copied().collect();
+        for pt in to_flip {
+            self.flip(pt, fold);
+        }
+        self.height = if fold.1 > 0 { fold.1 } else { self.height };
+        self.width = if fold.0 > 0 { fold.0 } else { self.width };
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let z = if self.dots.contains(&(x, y)) { '#' } else { '.' };
+                write!(f, "{}", z)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_dot(src: &Source, line: &str) -> Result<Pt> {
+    let (x, y) = src.split_once(line.trim(), ",")?;
+    Ok((src.parse(x, "integer")?, src.parse(y, "integer")?))
+}
+
+fn parse_fold(src: &Source, line: &str) -> Result<Pt> {
+    let fold = src.strip_prefix(line.trim(), "fold along ")?;
+    let (coord, val) = src.split_once(fold, "=")?;
+    let val = src.parse(val, "integer")?;
+    match coord {
+        "x" => Ok((val, 0)),
+        "y" => Ok((0, val)),
+        _ => Err(src.error(coord, "x or y")),
+    }
+}
+
+pub fn parse(s: &str) -> Result<(Grid, Vec<Pt>)> {
+    let src = Source::new(s);
+    let (dots, folds) = s
+        .split_once("\n\n")
+        .ok_or_else(|| src.eof("blank line before folds"))?;
+    let dots = dots.lines().map(|line| parse_dot(&src, line));
+    let dots = dots.collect::<Result<HashSet<Pt>>>()?;
+    let width = dots.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+    let folds = folds.lines().map(|line| parse_fold(&src, line));
+    let folds = folds.collect::<Result<Vec<Pt>>>()?;
+    if folds.is_empty() {
+        return Err(src.eof("fold instruction"));
+    }
+    Ok((Grid { dots, width, height }, folds))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = (Grid, Vec<Pt>);
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1((grid, folds): &(Grid, Vec<Pt>)) -> Solved {
+        let mut grid = grid.clone();
+        grid.fold(folds[0]);
+        Ok(grid.dots.len().into())
+    }
+
+    fn part2((grid, folds): &(Grid, Vec<Pt>)) -> Solved {
+        let mut grid = grid.clone();
+        folds.iter().for_each(|&fold| grid.fold(fold));
+        Ok(grid.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::Answer;
+
+    static INPUT: &str = "6,10
+                                  0,14
+                                  9,10
+                                  0,3
+                                  10,4
+                                  4,11
+                                  6,0
+                                  6,12
+                                  4,1
+                                  0,13
+                                  10,12
+                                  3,4
+                                  3,0
+                                  8,4
+                                  1,10
+                                  2,14
+                                  8,10
+                                  9,0
+
+                                  fold along y=7
+                                  fold along x=5";
+
+    #[test]
+    fn test_part1() {
+        let (mut grid, folds) = parse(INPUT).unwrap();
+        grid.fold(folds[0]);
+        assert_eq!(17, grid.dots.len());
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = Day13::parse(INPUT).unwrap();
+        let expected = "#####\n#...#\n#...#\n#...#\n#####\n.....\n.....\n";
+        let expected = Answer::Text(expected.to_string());
+        assert_eq!(Ok(expected), Day13::part2(&input));
+    }
+
+    #[test]
+    fn test_no_folds() {
+        let err = parse("6,10\n0,14\n\n").unwrap_err();
+        assert_eq!("fold instruction", err.expected);
+    }
+}
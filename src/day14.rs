@@ -0,0 +1,125 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+use std::collections::HashMap;
+
+pub type Pair = (u8, u8);
+type Freqs = HashMap<u8, i64>;
+
+fn merge(a: Freqs, b: Freqs) -> Freqs {
+    b.iter().fold(a, |mut acc, (&k, v)| {
+        *acc.entry(k).or_default() += v;
+        acc
+    })
+}
+
+fn expand_freqs(
+    input @ (left, right): Pair,
+    rules: &HashMap<Pair, u8>,
+    n: usize,
+    memo: &mut HashMap<(Pair, usize), Freqs>,
+) -> Freqs {
+    if n == 0 {
+        return Freqs::new();
+    } else if let Some(freqs) = memo.get(&(input, n)) {
+        return freqs.clone();
+    }
+    let &mid = rules.get(&input).unwrap();
+    let mut freqs = merge(
+        expand_freqs((left, mid), rules, n - 1, memo),
+        expand_freqs((mid, right), rules, n - 1, memo),
+    );
+    *freqs.entry(mid).or_default() += 1;
+    memo.entry((input, n)).or_insert(freqs).clone()
+}
+
+pub fn solve(input: &str, rules: &HashMap<Pair, u8>, n: usize) -> i64 {
+    // populate initial freqs from input string
+    let freqs = input.bytes().fold(Freqs::new(), |mut acc, ch| {
+        *acc.entry(ch).or_default() += 1;
+        acc
+    });
+
+    // fold together recursively expanded freqs for each pair in the input
+    let mut memo = HashMap::new();
+    let pairs = input.bytes().zip(input.bytes().skip(1));
+    let freqs = pairs.fold(freqs, |acc, pair| {
+        merge(acc, expand_freqs(pair, rules, n, &mut memo))
+    });
+
+    let most_common = freqs.values().max().unwrap();
+    let least_common = freqs.values().min().unwrap();
+    most_common - least_common
+}
+
+fn parse_rule(src: &Source, line: &str) -> Result<(Pair, u8)> {
+    let (pair, elem) = src.split_once(line, " -> ")?;
+    let (pair, elem) = (pair.trim(), elem.trim());
+    match (pair.as_bytes(), elem.as_bytes()) {
+        (&[a, b], &[c]) => Ok(((a, b), c)),
+        (&[_, _], _) => Err(src.error(elem, "single element")),
+        _ => Err(src.error(pair, "pair of elements")),
+    }
+}
+
+pub fn parse(s: &str) -> Result<(String, HashMap<Pair, u8>)> {
+    let src = Source::new(s);
+    let mut lines = s.lines();
+    let input = lines.next().ok_or_else(|| src.eof("polymer template"))?;
+    match lines.next() {
+        Some(line) if line.trim().is_empty() => {}
+        Some(line) => return Err(src.error(line, "empty line")),
+        None => return Err(src.eof("empty line")),
+    }
+    let rules = lines.map(|line| parse_rule(&src, line));
+    Ok((input.trim().to_string(), rules.collect::<Result<_>>()?))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = (String, HashMap<Pair, u8>);
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1((input, rules): &(String, HashMap<Pair, u8>)) -> Solved {
+        Ok(solve(input, rules, 10).into())
+    }
+
+    fn part2((input, rules): &(String, HashMap<Pair, u8>)) -> Solved {
+        Ok(solve(input, rules, 40).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "NNCB
+
+    CH -> B
+    HH -> N
+    CB -> H
+    NH -> C
+    HB -> C
+    HC -> B
+    HN -> C
+    NN -> C
+    BH -> H
+    NC -> B
+    NB -> B
+    BN -> B
+    BB -> N
+    BC -> B
+    CC -> N
+    CN -> C";
+
+    #[test]
+    fn test() {
+        let (input, rules) = parse(INPUT).unwrap();
+        println!("{}, {:?}", input, rules);
+        assert_eq!(1588, solve(&input, &rules, 10));
+        assert_eq!(2188189693529, solve(&input, &rules, 40));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::grid::Grid;
+use crate::parse::{Result, Source};
+use crate::pt::{self, manhattan, Pt};
+use crate::search;
+use crate::solution::{self, Solution, Solved};
+use std::num::NonZeroU8;
+
+pub type Graph = Grid<u8>;
+
+pub trait Cave {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn risk(&self, pt: Pt) -> Option<u8>;
+
+    fn nbrs4(&self, pt: Pt) -> impl Iterator<Item = Pt> + '_
+    where
+        Self: Sized,
+    {
+        pt::nbrs4(pt).filter(|&nbr| self.risk(nbr).is_some())
+    }
+}
+
+impl Cave for Graph {
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn risk(&self, pt: Pt) -> Option<u8> {
+        self.get(pt).copied()
+    }
+}
+
+// the base map repeated rows x cols times, each tile's risk raised by its
+// distance in tiles from the top left and wrapped from wrap back to 1.
+// risks are computed on the fly rather than stored.
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a> {
+    pub base: &'a Graph,
+    pub rows: usize,
+    pub cols: usize,
+    pub wrap: NonZeroU8,
+}
+
+impl<'a> Tiled<'a> {
+    pub fn new(
+        base: &'a Graph,
+        rows: usize,
+        cols: usize,
+        wrap: NonZeroU8,
+    ) -> Self {
+        Tiled { base, rows, cols, wrap }
+    }
+
+    // the full part 2 map: five tiles each way, wrapping after 9
+    pub fn part2(base: &'a Graph) -> Self {
+        const NINE: NonZeroU8 = NonZeroU8::new(9).unwrap();
+        Tiled::new(base, 5, 5, NINE)
+    }
+
+    pub fn to_graph(&self) -> Graph {
+        let rows = (0..self.height() as i32).map(|r| {
+            let row = (0..self.width() as i32).map(|c| self.risk((r, c)));
+            row.map(Option::unwrap).collect()
+        });
+        Grid::from_rows(rows.collect())
+    }
+}
+
+impl Cave for Tiled<'_> {
+    fn width(&self) -> usize {
+        self.base.width() * self.cols
+    }
+
+    fn height(&self) -> usize {
+        self.base.height() * self.rows
+    }
+
+    fn risk(&self, (r, c): Pt) -> Option<u8> {
+        let (h, w) = (self.base.height() as i32, self.base.width() as i32);
+        if r < 0
+            || c < 0
+            || r >= self.height() as i32
+            || c >= self.width() as i32
+        {
+            return None;
+        }
+        let val = self.base[(r % h, c % w)] as i32 + r / h + c / w;
+        let wrap = self.wrap.get() as i32;
+        Some(((val - 1).rem_euclid(wrap) + 1) as u8)
+    }
+}
+
+// the total risk and the points along the path, including both ends
+pub fn shortest_path(
+    cave: &impl Cave,
+    from: Pt,
+    to: Pt,
+) -> Option<(i64, Vec<Pt>)> {
+    // every step costs at least 1
+    search::astar(
+        from,
+        |&pt| pt == to,
+        |&pt| cave.nbrs4(pt).map(|nbr| (nbr, cave.risk(nbr).unwrap() as i64)),
+        |&pt| manhattan(pt, to) as i64,
+    )
+}
+
+pub fn min_risk_path(cave: &impl Cave) -> (i64, Vec<Pt>) {
+    let to = (cave.height() as i32 - 1, cave.width() as i32 - 1);
+    shortest_path(cave, (0, 0), to).unwrap()
+}
+
+pub fn min_risk(cave: &impl Cave) -> i64 {
+    min_risk_path(cave).0
+}
+
+// shows the risk along the path and dots elsewhere, or with color, every
+// risk level with the path in bold. once risks pass 9, every cell is as
+// wide as the widest risk and cells are separated by spaces.
+pub fn render(cave: &impl Cave, path: &[Pt], color: bool) -> String {
+    let mut on_path = Grid::new(cave.width(), cave.height(), false);
+    path.iter().for_each(|&pt| on_path[pt] = true);
+    let risks = on_path.iter().map(|(pt, _)| cave.risk(pt).unwrap());
+    let w = risks.max().map_or(1, |risk| risk.to_string().len());
+    let mut s = String::new();
+    for (pt, &on_path) in on_path.iter() {
+        let risk = format!("{:>w$}", cave.risk(pt).unwrap());
+        if w > 1 && pt.1 > 0 {
+            s.push(' ');
+        }
+        match (on_path, color) {
+            (true, true) => s += &format!("\x1b[1m{}\x1b[0m", risk),
+            (false, true) => s += &format!("\x1b[2m{}\x1b[0m", risk),
+            (true, false) => s += &risk,
+            (false, false) => s += &format!("{:>w$}", "."),
+        }
+        if pt.1 as usize == cave.width() - 1 {
+            s.push('\n');
+        }
+    }
+    s
+}
+
+pub fn parse(s: &str) -> Result<Graph> {
+    let graph = Graph::parse_digits(s)?;
+    if graph.is_empty() {
+        return Err(Source::new(s).eof("digit"));
+    }
+    Ok(graph)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Graph;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(graph: &Graph) -> Solved {
+        Ok(min_risk(graph).into())
+    }
+
+    fn part2(graph: &Graph) -> Solved {
+        Ok(min_risk(&Tiled::part2(graph)).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    fn inc_round(mut val: u8, i: u8, j: u8) -> u8 {
+        let inc = |val| if val + 1 == 10 { 1 } else { val + 1 };
+        val = (0..i).fold(val, |val, _| inc(val));
+        (0..j).fold(val, |val, _| inc(val))
+    }
+
+    // the full part 2 map, built tile by tile, to check Tiled against
+    fn expand(graph: &Graph) -> Graph {
+        let (height, width) = (graph.height(), graph.width());
+        let mut expanded = Graph::new(width * 5, height * 5, 0);
+        for i in 0..5 {
+            for j in 0..5 {
+                for ((row, col), &val) in graph.iter() {
+                    let row = i * height as i32 + row;
+                    let col = j * width as i32 + col;
+                    expanded[(row, col)] = inc_round(val, i as u8, j as u8);
+                }
+            }
+        }
+        expanded
+    }
+
+    fn wrap(n: u8) -> NonZeroU8 {
+        NonZeroU8::new(n).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(40, min_risk(&parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_tiled_wrap() {
+        let graph = parse("8").unwrap();
+        let tiled = Tiled::new(&graph, 1, 5, wrap(12));
+        let row: Vec<_> = (0..5).map(|c| tiled.risk((0, c)).unwrap()).collect();
+        assert_eq!(vec![8, 9, 10, 11, 12], row);
+        let tiled = Tiled::new(&graph, 1, 6, wrap(10));
+        let path: Vec<Pt> = (0..6).map(|c| (0, c)).collect();
+        assert_eq!(" 8  9 10  1  2  3\n", render(&tiled, &path, false));
+        assert_eq!(" .  . 10  .  .  .\n", render(&tiled, &path[2..3], false));
+        let (risk, _) = min_risk_path(&tiled);
+        assert_eq!(25, risk);
+
+        let graph = parse("9").unwrap();
+        let tiled = Tiled::new(&graph, 1, 250, wrap(255));
+        assert_eq!(Some(255), tiled.risk((0, 246)));
+        assert_eq!(Some(1), tiled.risk((0, 247)));
+    }
+
+    #[test]
+    fn test_tiled() {
+        let graph = parse("8").unwrap();
+        let tiled = Tiled::new(&graph, 2, 5, wrap(9));
+        assert_eq!((5, 2), (tiled.width(), tiled.height()));
+        let row: Vec<_> = (0..5).map(|c| tiled.risk((1, c)).unwrap()).collect();
+        assert_eq!(vec![9, 1, 2, 3, 4], row);
+        assert_eq!(None, tiled.risk((2, 0)));
+        let tiled = Tiled::new(&graph, 1, 3, wrap(8));
+        assert_eq!("812\n", render(&tiled, &[(0, 0), (0, 1), (0, 2)], false));
+
+        let graph = parse(INPUT).unwrap();
+        let tiled = Tiled::part2(&graph);
+        let expanded = tiled.to_graph();
+        assert_eq!(expand(&graph), expanded);
+        let row: String =
+            (0..50).map(|c| expanded[(0, c)].to_string()).collect();
+        assert_eq!("11637517422274862853338597396444961841755517295286", row);
+        assert_eq!(315, min_risk(&tiled));
+        let (risk, path) = min_risk_path(&Tiled::new(&graph, 1, 2, wrap(9)));
+        assert_eq!((9, 19), *path.last().unwrap());
+        assert!(risk > 40);
+    }
+
+    #[test]
+    fn test_path() {
+        let graph = parse(INPUT).unwrap();
+        let (risk, path) = min_risk_path(&graph);
+        assert_eq!((0, 0), path[0]);
+        assert_eq!((9, 9), *path.last().unwrap());
+        assert_eq!(risk, path[1..].iter().map(|&pt| graph[pt] as i64).sum());
+        assert_eq!(
+            path.len(),
+            render(&graph, &path, false).matches(char::is_numeric).count()
+        );
+        let (risk, path) = min_risk_path(&expand(&graph));
+        assert_eq!(315, risk);
+        assert_eq!((49, 49), *path.last().unwrap());
+        assert!(path.windows(2).all(|w| manhattan(w[0], w[1]) == 1));
+        let graph = parse("191\n111\n991").unwrap();
+        let (_, path) = min_risk_path(&graph);
+        assert_eq!("1..\n111\n..1\n", render(&graph, &path, false));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(315, min_risk(&expand(&parse(INPUT).unwrap())));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!("digit", parse("").unwrap_err().expected);
+        assert_eq!("digit", parse("\n").unwrap_err().expected);
+    }
+}
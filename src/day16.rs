@@ -0,0 +1,998 @@
+use crate::parse::{ParseError, Source};
+use crate::solution::{self, Answer, Solution, Solved};
+use std::io::Read;
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum Error {
+    // the bit offset the read started at
+    EofError(usize),
+    InvalidTypeId(u64),
+    InvalidLengthTypeId(u64),
+    InvalidHexDigit(char),
+    Io(std::io::Error),
+    // the bit offset after which the stream isn't whole packets
+    TrailingGarbage(usize),
+    Unencodable(&'static str),
+    // evaluation errors, with the bit offset of the offending packet
+    Overflow(usize),
+    NoOperands(usize),
+    WrongArity(usize, usize),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::EofError(at) => write!(f, "unexpected end at bit {}", at),
+            Error::InvalidTypeId(id) => write!(f, "invalid type id {}", id),
+            Error::InvalidLengthTypeId(id) => {
+                write!(f, "invalid length type id {}", id)
+            }
+            Error::InvalidHexDigit(ch) => {
+                write!(f, "invalid hex digit {:?}", ch)
+            }
+            Error::Io(err) => write!(f, "{}", err),
+            Error::TrailingGarbage(at) => {
+                write!(f, "trailing garbage at bit {}", at)
+            }
+            Error::Unencodable(why) => write!(f, "can't encode: {}", why),
+            Error::Overflow(at) => write!(f, "overflow at bit {}", at),
+            Error::NoOperands(at) => {
+                write!(f, "operator without operands at bit {}", at)
+            }
+            Error::WrongArity(at, n) => {
+                write!(f, "comparison of {} operands at bit {}", n, at)
+            }
+        }
+    }
+}
+
+// how bytes from a reader become bits
+enum Encoding {
+    Hex,
+    Raw,
+}
+
+pub struct BitStream {
+    i: usize,
+    len: usize,
+    // bit offsets are from the start of the stream, but bytes before
+    // start have been dropped
+    start: usize,
+    bytes: Vec<u8>,
+    reader: Option<(Box<dyn Read>, Encoding)>,
+}
+
+impl BitStream {
+    fn new(reader: Option<(Box<dyn Read>, Encoding)>) -> BitStream {
+        BitStream { i: 0, len: 0, start: 0, bytes: Vec::new(), reader }
+    }
+
+    // a hex transmission read on demand; whitespace is ignored
+    pub fn from_hex_reader(r: impl Read + 'static) -> BitStream {
+        BitStream::new(Some((Box::new(r), Encoding::Hex)))
+    }
+
+    // a raw binary transmission read on demand
+    pub fn from_reader(r: impl Read + 'static) -> BitStream {
+        BitStream::new(Some((Box::new(r), Encoding::Raw)))
+    }
+
+    pub fn pos(&self) -> usize {
+        self.i
+    }
+
+    // the bits read so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push_nibble(&mut self, x: u8) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(x << 4);
+        } else {
+            *self.bytes.last_mut().unwrap() |= x;
+        }
+        self.len += 4;
+    }
+
+    fn push_byte(&mut self, x: u8) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(x);
+            self.len += 8;
+        } else {
+            self.push_nibble(x >> 4);
+            self.push_nibble(x & 0xf);
+        }
+    }
+
+    // reads until at least n bits are buffered or the reader is done
+    fn fill(&mut self, n: usize) -> Result<(), Error> {
+        let mut buf = [0; 4096];
+        while self.len < n {
+            let Some((r, encoding)) = &mut self.reader else {
+                return Ok(());
+            };
+            let read = r.read(&mut buf).map_err(Error::Io)?;
+            let hex = matches!(encoding, Encoding::Hex);
+            if read == 0 {
+                self.reader = None;
+            }
+            for &x in &buf[..read] {
+                match x as char {
+                    _ if !hex => self.push_byte(x),
+                    ch if ch.is_ascii_whitespace() => {}
+                    ch => match ch.to_digit(16) {
+                        Some(x) => self.push_nibble(x as u8),
+                        None => return Err(Error::InvalidHexDigit(ch)),
+                    },
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn byte_at(&self, i: usize) -> u8 {
+        self.bytes[i / 8 - self.start]
+    }
+
+    // forgets the bytes before the current one
+    fn discard(&mut self) {
+        let n = self.i / 8 - self.start;
+        self.bytes.drain(..n);
+        self.start += n;
+    }
+
+    // the next width bits, msb first, as a number; width is at most 64
+    fn eat_num(&mut self, width: usize) -> Result<u64, Error> {
+        assert!(width <= 64);
+        self.fill(self.i + width)?;
+        if self.i + width > self.len {
+            return Err(Error::EofError(self.i));
+        }
+        let mut val = 0u64;
+        let mut left = width;
+        while left > 0 {
+            let avail = 8 - self.i % 8;
+            let take = avail.min(left);
+            let byte = (self.byte_at(self.i) >> (avail - take)) as u64;
+            val = val << take | byte & ((1 << take) - 1);
+            self.i += take;
+            left -= take;
+        }
+        Ok(val)
+    }
+
+    // the offset past the whole zero bytes starting at the current byte
+    // boundary, or None if they run to the end
+    fn zero_bytes_end(&mut self) -> Result<Option<usize>, Error> {
+        let mut i = self.i;
+        loop {
+            self.fill(i + 8)?;
+            if i >= self.len {
+                return Ok(None);
+            }
+            if self.byte_at(i) != 0 {
+                return Ok(Some(i));
+            }
+            i = self.len.min(i + 8);
+        }
+    }
+}
+
+// a raw binary transmission
+impl From<Vec<u8>> for BitStream {
+    fn from(bytes: Vec<u8>) -> Self {
+        BitStream { len: bytes.len() * 8, bytes, ..BitStream::new(None) }
+    }
+}
+
+impl std::str::FromStr for BitStream {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = Source::new(s);
+        let s = s.trim();
+        let hex = |(i, ch): (usize, char)| {
+            let x = ch.to_digit(16).map(|x| x as u8);
+            x.ok_or_else(|| src.error(&s[i..], "hex digit"))
+        };
+        let mut bits = BitStream::new(None);
+        let digits = s.char_indices().filter(|(_, ch)| !ch.is_whitespace());
+        for digit in digits.map(hex) {
+            bits.push_nibble(digit?);
+        }
+        Ok(bits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u64,
+    pub payload: Payload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    Literal(u64),
+    Add(Vec<Packet>),
+    Mul(Vec<Packet>),
+    Min(Vec<Packet>),
+    Max(Vec<Packet>),
+    Gt(Vec<Packet>),
+    Lt(Vec<Packet>),
+    Eq(Vec<Packet>),
+}
+
+impl Payload {
+    pub fn type_id(&self) -> u64 {
+        match self {
+            Payload::Add(_) => 0,
+            Payload::Mul(_) => 1,
+            Payload::Min(_) => 2,
+            Payload::Max(_) => 3,
+            Payload::Literal(_) => 4,
+            Payload::Gt(_) => 5,
+            Payload::Lt(_) => 6,
+            Payload::Eq(_) => 7,
+        }
+    }
+
+    pub fn packets(&self) -> &[Packet] {
+        match self {
+            Payload::Literal(_) => &[],
+            Payload::Add(packets)
+            | Payload::Mul(packets)
+            | Payload::Max(packets)
+            | Payload::Min(packets)
+            | Payload::Gt(packets)
+            | Payload::Lt(packets)
+            | Payload::Eq(packets) => packets,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Payload::Add(_) => "sum",
+            Payload::Mul(_) => "product",
+            Payload::Min(_) => "min",
+            Payload::Max(_) => "max",
+            Payload::Literal(_) => "literal",
+            Payload::Gt(_) => "gt",
+            Payload::Lt(_) => "lt",
+            Payload::Eq(_) => "eq",
+        }
+    }
+
+    // binding strength as an infix expression; 4 is an atom
+    fn prec(&self) -> u8 {
+        match self {
+            Payload::Add(packets) | Payload::Mul(packets)
+                if packets.len() < 2 =>
+            {
+                4
+            }
+            Payload::Gt(_) | Payload::Lt(_) | Payload::Eq(_) => 1,
+            Payload::Add(_) => 2,
+            Payload::Mul(_) => 3,
+            _ => 4,
+        }
+    }
+}
+
+fn write_operands(
+    f: &mut std::fmt::Formatter,
+    packets: &[Packet],
+    sep: &str,
+    prec: u8,
+) -> std::fmt::Result {
+    for (i, packet) in packets.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", sep)?;
+        }
+        if packet.payload.prec() < prec {
+            write!(f, "({})", packet)?;
+        } else {
+            write!(f, "{}", packet)?;
+        }
+    }
+    Ok(())
+}
+
+// the infix expression the packet evaluates
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let payload = &self.payload;
+        let packets = payload.packets();
+        match payload {
+            Payload::Literal(val) => write!(f, "{}", val),
+            Payload::Add(_) | Payload::Mul(_) if packets.len() >= 2 => {
+                let sep = if payload.prec() == 2 { " + " } else { " * " };
+                write_operands(f, packets, sep, payload.prec())
+            }
+            Payload::Gt(_) => write_operands(f, packets, " > ", 2),
+            Payload::Lt(_) => write_operands(f, packets, " < ", 2),
+            Payload::Eq(_) => write_operands(f, packets, " == ", 2),
+            _ => {
+                write!(f, "{}(", payload.name())?;
+                write_operands(f, packets, ", ", 0)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// where a packet sat in the transmission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub offset: usize,
+    pub length_type: Option<LengthType>,
+}
+
+struct PacketParser {
+    bits: BitStream,
+    // one per packet, in pre-order
+    layout: Vec<Layout>,
+}
+
+impl PacketParser {
+    fn parse_literal(&mut self) -> Result<u64, Error> {
+        let mut val = 0;
+        let mut done = false;
+        while !done {
+            done = self.bits.eat_num(1)? == 0;
+            val = (val << 4) | self.bits.eat_num(4)?;
+        }
+        Ok(val)
+    }
+
+    fn parse_packets_by_count(&mut self) -> Result<Vec<Packet>, Error> {
+        let count = self.bits.eat_num(11)?;
+        (0..count).map(|_| self.parse_packet()).collect()
+    }
+
+    fn parse_packets_by_len(&mut self) -> Result<Vec<Packet>, Error> {
+        let len = self.bits.eat_num(15)? as usize;
+        let start = self.bits.i;
+        let mut packets = Vec::new();
+        while self.bits.i < start + len {
+            packets.push(self.parse_packet()?);
+        }
+        Ok(packets)
+    }
+
+    fn parse_packets(&mut self) -> Result<Vec<Packet>, Error> {
+        let length_type_id = self.bits.eat_num(1)?;
+        self.layout.last_mut().unwrap().length_type = match length_type_id {
+            0 => Some(LengthType::Bits),
+            _ => Some(LengthType::Count),
+        };
+        match length_type_id {
+            0 => self.parse_packets_by_len(),
+            1 => self.parse_packets_by_count(),
+            n => Err(Error::InvalidLengthTypeId(n)),
+        }
+    }
+
+    fn parse_packet(&mut self) -> Result<Packet, Error> {
+        let offset = self.bits.i;
+        self.layout.push(Layout { offset, length_type: None });
+        let version = self.bits.eat_num(3)?;
+        let type_id = self.bits.eat_num(3)?;
+        let payload = match type_id {
+            0 => Payload::Add(self.parse_packets()?),
+            1 => Payload::Mul(self.parse_packets()?),
+            2 => Payload::Min(self.parse_packets()?),
+            3 => Payload::Max(self.parse_packets()?),
+            4 => Payload::Literal(self.parse_literal()?),
+            5 => Payload::Gt(self.parse_packets()?),
+            6 => Payload::Lt(self.parse_packets()?),
+            7 => Payload::Eq(self.parse_packets()?),
+            n => return Err(Error::InvalidTypeId(n)),
+        };
+        Ok(Packet { version, payload })
+    }
+}
+
+pub fn version_sum(packet: &Packet) -> u64 {
+    let sub_sum: u64 = packet.payload.packets().iter().map(version_sum).sum();
+    sub_sum + packet.version
+}
+
+// successive top-level packets, each padded with zero bits to a whole byte.
+// zero bytes between packets are skipped up to the first one a packet
+// parses from, and zeros after the last packet are ignored; anything else
+// is an error.
+pub struct Packets {
+    parser: PacketParser,
+    done: bool,
+}
+
+impl Packets {
+    // a packet starting at the current bit, along with its padding
+    fn padded_packet(&mut self) -> Result<Packet, Error> {
+        let start = self.parser.bits.i;
+        self.parser.layout.clear();
+        let packet = match self.parser.parse_packet() {
+            Err(Error::EofError(_)) => {
+                return Err(Error::TrailingGarbage(start))
+            }
+            result => result?,
+        };
+        let bits = &mut self.parser.bits;
+        let pad = (8 - bits.i % 8) % 8;
+        bits.fill(bits.i + pad)?;
+        let at = bits.i;
+        if bits.eat_num(pad.min(bits.len - at))? != 0 {
+            return Err(Error::TrailingGarbage(at));
+        }
+        Ok(packet)
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Packet>, Error> {
+        let bits = &mut self.parser.bits;
+        bits.discard();
+        let Some(end) = bits.zero_bytes_end()? else {
+            return Ok(None);
+        };
+        // three zero bytes parse as an empty version 0 sum, so that's taken
+        // as more zeros rather than a packet
+        let empty = |packet: &Packet| {
+            packet.version == 0 && packet.payload == Payload::Add(vec![])
+        };
+        // the packet may start at any of the zero bytes, its own leading
+        // zeros being the rest
+        loop {
+            let at = self.parser.bits.i;
+            match self.padded_packet() {
+                Err(Error::Io(err)) => return Err(Error::Io(err)),
+                Err(Error::InvalidHexDigit(ch)) => {
+                    return Err(Error::InvalidHexDigit(ch))
+                }
+                Ok(packet) if !empty(&packet) => return Ok(Some(packet)),
+                result if at >= end => return result.map(Some),
+                _ => self.parser.bits.i = at + 8,
+            }
+        }
+    }
+}
+
+impl Iterator for Packets {
+    type Item = Result<Packet, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.next_packet().transpose();
+        self.done = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
+
+pub fn packets(bits: BitStream) -> Packets {
+    let parser = PacketParser { bits, layout: Vec::new() };
+    Packets { parser, done: false }
+}
+
+// the integer type a checked evaluation is carried out in
+pub trait Word: Copy + Ord + From<u64> + From<bool> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_word!(u64, u128);
+
+fn checked_eval_packet<'a, W: Word>(
+    packet: &Packet,
+    layout: &mut impl Iterator<Item = &'a Layout>,
+) -> Result<W, Error> {
+    let at = layout.next().unwrap().offset;
+    let vals = packet
+        .payload
+        .packets()
+        .iter()
+        .map(|packet| checked_eval_packet(packet, layout))
+        .collect::<Result<Vec<W>, _>>()?;
+    if vals.is_empty() && !matches!(packet.payload, Payload::Literal(_)) {
+        return Err(Error::NoOperands(at));
+    }
+    let cmp = |f: fn(&W, &W) -> bool| match vals[..] {
+        [a, b] => Ok(W::from(f(&a, &b))),
+        _ => Err(Error::WrongArity(at, vals.len())),
+    };
+    let fold = |init: u64, f: fn(W, W) -> Option<W>| {
+        let mut vals = vals.iter();
+        vals.try_fold(W::from(init), |acc, &x| f(acc, x))
+            .ok_or(Error::Overflow(at))
+    };
+    match packet.payload {
+        Payload::Literal(val) => Ok(W::from(val)),
+        Payload::Add(_) => fold(0, W::checked_add),
+        Payload::Mul(_) => fold(1, W::checked_mul),
+        Payload::Min(_) => Ok(*vals.iter().min().unwrap()),
+        Payload::Max(_) => Ok(*vals.iter().max().unwrap()),
+        Payload::Gt(_) => cmp(W::gt),
+        Payload::Lt(_) => cmp(W::lt),
+        Payload::Eq(_) => cmp(W::eq),
+    }
+}
+
+// a decoded packet along with the layout of it and each sub-packet, in
+// pre-order
+#[derive(Debug, Clone)]
+pub struct Transmission {
+    packet: Packet,
+    layout: Vec<Layout>,
+}
+
+impl Transmission {
+    pub fn packet(&self) -> &Packet {
+        &self.packet
+    }
+
+    pub fn layout(&self) -> &[Layout] {
+        &self.layout
+    }
+
+    // the value of the packet in W, failing on overflow, operators without
+    // operands and comparisons without exactly two
+    pub fn eval<W: Word>(&self) -> Result<W, Error> {
+        checked_eval_packet(&self.packet, &mut self.layout.iter())
+    }
+}
+
+pub fn checked_eval<W: Word>(bits: BitStream) -> Result<W, Error> {
+    decode(bits)?.eval()
+}
+
+pub fn parse(bits: BitStream) -> Result<Packet, Error> {
+    decode(bits).map(|transmission| transmission.packet)
+}
+
+pub fn decode(bits: BitStream) -> Result<Transmission, Error> {
+    let mut parser = PacketParser { bits, layout: Vec::new() };
+    let packet = parser.parse_packet()?;
+    Ok(Transmission { packet, layout: parser.layout })
+}
+
+fn write_tree<'a>(
+    out: &mut String,
+    packet: &Packet,
+    layout: &mut impl Iterator<Item = &'a Layout>,
+    depth: usize,
+) {
+    let Layout { offset, length_type } = layout.next().unwrap();
+    let payload = &packet.payload;
+    out.push_str(&format!(
+        "{:indent$}@{} v{} type {} ({})",
+        "",
+        offset,
+        packet.version,
+        payload.type_id(),
+        payload.name(),
+        indent = 2 * depth,
+    ));
+    match (payload, length_type) {
+        (Payload::Literal(val), _) => out.push_str(&format!(" {}\n", val)),
+        (_, Some(length_type)) => {
+            let id = (*length_type == LengthType::Count) as u8;
+            out.push_str(&format!(" length type {}\n", id));
+        }
+        (_, None) => out.push('\n'),
+    }
+    for packet in payload.packets() {
+        write_tree(out, packet, layout, depth + 1);
+    }
+}
+
+// one line per packet, indented by depth, giving the bit offset it starts
+// at, its version and type id, and how its sub-packets are delimited
+pub fn disassemble(bits: BitStream) -> Result<String, Error> {
+    let Transmission { packet, layout } = decode(bits)?;
+    let mut out = String::new();
+    write_tree(&mut out, &packet, &mut layout.iter(), 0);
+    Ok(out)
+}
+
+// how an operator packet says where its sub-packets end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    Bits,
+    Count,
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    fn push_num(&mut self, val: u64, width: usize) {
+        self.bits.extend((0..width).rev().map(|i| val >> i & 1 == 1));
+    }
+
+    // zero-padded to whole bytes, like the puzzle's transmissions
+    fn to_hex(&self) -> String {
+        let mut bits = self.bits.clone();
+        bits.resize(bits.len().div_ceil(8) * 8, false);
+        let digit = |chunk: &[bool]| {
+            let x = (0..4).fold(0, |x, i| {
+                x << 1 | *chunk.get(i).unwrap_or(&false) as u32
+            });
+            char::from_digit(x, 16).unwrap().to_ascii_uppercase()
+        };
+        bits.chunks(4).map(digit).collect()
+    }
+}
+
+fn encode_packet(
+    w: &mut BitWriter,
+    packet: &Packet,
+    length_type: LengthType,
+) -> Result<(), Error> {
+    if packet.version >= 1 << 3 {
+        return Err(Error::Unencodable("version wider than 3 bits"));
+    }
+    w.push_num(packet.version, 3);
+    w.push_num(packet.payload.type_id(), 3);
+    if let Payload::Literal(val) = packet.payload {
+        // as few 4-bit groups as hold the value, but at least one
+        let groups = (64 - val.leading_zeros() as usize).div_ceil(4).max(1);
+        for i in (0..groups).rev() {
+            w.push_num((i > 0) as u64, 1);
+            w.push_num(val >> (4 * i) & 0xf, 4);
+        }
+        return Ok(());
+    }
+    let packets = packet.payload.packets();
+    let mut sub = BitWriter::default();
+    for packet in packets {
+        encode_packet(&mut sub, packet, length_type)?;
+    }
+    match length_type {
+        LengthType::Bits if sub.bits.len() >= 1 << 15 => {
+            return Err(Error::Unencodable("sub-packets longer than 15 bits"))
+        }
+        LengthType::Bits => {
+            w.push_num(0, 1);
+            w.push_num(sub.bits.len() as u64, 15);
+        }
+        LengthType::Count if packets.len() >= 1 << 11 => {
+            return Err(Error::Unencodable("sub-packet count over 11 bits"))
+        }
+        LengthType::Count => {
+            w.push_num(1, 1);
+            w.push_num(packets.len() as u64, 11);
+        }
+    }
+    w.bits.extend(sub.bits);
+    Ok(())
+}
+
+// the hex transmission for packet, using length_type for every operator
+pub fn encode(
+    packet: &Packet,
+    length_type: LengthType,
+) -> Result<String, Error> {
+    let mut w = BitWriter::default();
+    encode_packet(&mut w, packet, length_type)?;
+    Ok(w.to_hex())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Transmission;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        let src = Source::new(s.trim_end());
+        decode(s.parse()?).map_err(|err| match err {
+            Error::EofError(at) => {
+                src.eof(format!("more packet bits at bit {}", at))
+            }
+            err => src.error(s, format!("valid packet ({:?})", err)),
+        })
+    }
+
+    fn part1(transmission: &Transmission) -> Solved {
+        Ok(version_sum(transmission.packet()).into())
+    }
+
+    fn part2(transmission: &Transmission) -> Solved {
+        let val = transmission.eval::<u128>();
+        val.map(Answer::from)
+            .map_err(|err| solution::Error::Solve(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        for (result, input) in &[
+            (16, "8A004A801A8002F478"),
+            (12, "620080001611562C8802118E34"),
+            (23, "C0015000016115A2E0802F182340"),
+            (31, "A0016C880162017C3686B18A3D4780"),
+        ] {
+            let bits = input.parse().unwrap();
+            let packet = parse(bits).unwrap();
+            assert_eq!(*result, version_sum(&packet));
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for (result, input) in &[
+            (3, "C200B40A82"),
+            (54, "04005AC33890"),
+            (7, "880086C3E88112"),
+            (9, "CE00C43D881120"),
+            (1, "D8005AC2A8F0"),
+            (0, "F600BC2D8F"),
+            (0, "9C005AC2F8F0"),
+            (1, "9C0141080250320F1802104A08"),
+        ] {
+            let transmission = decode(input.parse().unwrap()).unwrap();
+            assert_eq!(*result, transmission.eval::<u64>().unwrap());
+            assert_eq!(*result as u128, transmission.eval::<u128>().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bit_stream() {
+        let mut bits = BitStream::from(vec![0xff; 9]);
+        assert_eq!(7, bits.eat_num(3).unwrap());
+        assert_eq!(u64::MAX, bits.eat_num(64).unwrap());
+        assert_eq!(67, bits.pos());
+        assert!(matches!(bits.eat_num(6), Err(Error::EofError(67))));
+        assert_eq!(0x1f, bits.eat_num(5).unwrap());
+
+        let mut bits: BitStream = "A5C".parse().unwrap();
+        assert_eq!(12, bits.len());
+        assert_eq!(0b10, bits.eat_num(2).unwrap());
+        assert_eq!(0b1001011100, bits.eat_num(10).unwrap());
+        assert!(matches!(bits.eat_num(1), Err(Error::EofError(12))));
+
+        let packet = parse(BitStream::from(vec![0xd2, 0xfe, 0x28])).unwrap();
+        assert_eq!(Payload::Literal(2021), packet.payload);
+        let bits = "D2FE".parse().unwrap();
+        assert!(matches!(parse(bits), Err(Error::EofError(16))));
+    }
+
+    fn lit(val: u64) -> Packet {
+        Packet { version: 0, payload: Payload::Literal(val) }
+    }
+
+    fn op(payload: fn(Vec<Packet>) -> Payload, packets: Vec<Packet>) -> Packet {
+        Packet { version: 0, payload: payload(packets) }
+    }
+
+    #[test]
+    fn test_expression() {
+        let sum = op(
+            Payload::Add,
+            vec![lit(3), op(Payload::Max, vec![lit(7), lit(9)])],
+        );
+        let lt = op(Payload::Lt, vec![lit(5), lit(15)]);
+        let packet = op(Payload::Mul, vec![sum.clone(), lt.clone()]);
+        assert_eq!("(3 + max(7, 9)) * (5 < 15)", packet.to_string());
+        let packet =
+            op(Payload::Add, vec![lt, op(Payload::Mul, vec![lit(2), sum])]);
+        assert_eq!("(5 < 15) + 2 * (3 + max(7, 9))", packet.to_string());
+        let packet = op(
+            Payload::Eq,
+            vec![op(Payload::Mul, vec![lit(1)]), op(Payload::Add, vec![])],
+        );
+        assert_eq!("product(1) == sum()", packet.to_string());
+
+        let packet =
+            parse("9C0141080250320F1802104A08".parse().unwrap()).unwrap();
+        assert_eq!("1 + 3 == 2 * 2", packet.to_string());
+    }
+
+    fn checked<W: Word>(packet: &Packet) -> Result<W, Error> {
+        let bits = encode(packet, LengthType::Count).unwrap().parse().unwrap();
+        checked_eval(bits)
+    }
+
+    #[test]
+    fn test_checked_eval() {
+        let packet = op(
+            Payload::Mul,
+            vec![lit(1 << 40), op(Payload::Add, vec![lit(1 << 40), lit(1)])],
+        );
+        assert!(matches!(checked::<u64>(&packet), Err(Error::Overflow(0))));
+        assert_eq!((1 << 80) + (1 << 40), checked::<u128>(&packet).unwrap());
+        let packet = op(Payload::Add, vec![lit(u64::MAX), lit(1)]);
+        assert!(matches!(checked::<u64>(&packet), Err(Error::Overflow(0))));
+        assert_eq!(1 << 64, checked::<u128>(&packet).unwrap());
+
+        let packet = op(Payload::Add, vec![lit(1), op(Payload::Max, vec![])]);
+        assert!(matches!(
+            checked::<u64>(&packet),
+            Err(Error::NoOperands(29))
+        ));
+        let packet = op(Payload::Gt, vec![lit(3), lit(2), lit(1)]);
+        assert!(matches!(
+            checked::<u64>(&packet),
+            Err(Error::WrongArity(0, 3))
+        ));
+        let packet = op(Payload::Eq, vec![lit(3)]);
+        assert!(matches!(
+            checked::<u64>(&packet),
+            Err(Error::WrongArity(0, 1))
+        ));
+
+        let packet = op(Payload::Max, vec![]);
+        let hex = encode(&packet, LengthType::Bits).unwrap();
+        let transmission = Day16::parse(&hex).unwrap();
+        let err = "operator without operands at bit 0".to_string();
+        let err = solution::Error::Solve(err);
+        assert_eq!(Err(err), Day16::part2(&transmission));
+        let packet = op(Payload::Mul, vec![lit(u64::MAX), lit(u64::MAX)]);
+        let hex = encode(&packet, LengthType::Bits).unwrap();
+        let transmission = Day16::parse(&hex).unwrap();
+        let val = u64::MAX as u128 * u64::MAX as u128;
+        assert_eq!(Ok(Answer::from(val)), Day16::part2(&transmission));
+    }
+
+    // hands out a byte at a time, to cross every buffer boundary
+    struct Trickle(Vec<u8>);
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0.remove(0);
+            Ok(1)
+        }
+    }
+
+    fn stream(bits: BitStream) -> Result<Vec<Packet>, Error> {
+        packets(bits).collect()
+    }
+
+    #[test]
+    fn test_packets() {
+        let inputs = ["D2FE28", "38006F45291200", "9C0141080250320F1802104A08"];
+        let expected: Vec<Packet> = inputs
+            .iter()
+            .map(|input| parse(input.parse().unwrap()).unwrap())
+            .collect();
+        let log =
+            format!("{}\n{}\n{}\n0000\n", inputs[0], inputs[1], inputs[2]);
+        assert_eq!(expected, stream(log.parse().unwrap()).unwrap());
+        let bits = BitStream::from_hex_reader(Trickle(log.into_bytes()));
+        assert_eq!(expected, stream(bits).unwrap());
+        let raw = vec![0xd2, 0xfe, 0x28, 0xd2, 0xfe, 0x28, 0, 0];
+        let bits = BitStream::from_reader(std::io::Cursor::new(raw));
+        assert_eq!(vec![expected[0].clone(); 2], stream(bits).unwrap());
+        assert!(stream("".parse().unwrap()).unwrap().is_empty());
+        let bits = "D2FE2800\nD2FE28 0000 0000".parse().unwrap();
+        assert_eq!(vec![expected[0].clone(); 2], stream(bits).unwrap());
+        let bits =
+            BitStream::from_hex_reader(Trickle(b"000000D2FE28".to_vec()));
+        assert_eq!(vec![expected[0].clone()], stream(bits).unwrap());
+
+        // a version 0 sum starts with a zero byte
+        let v1 = |val| Packet { version: 1, payload: Payload::Literal(val) };
+        let packet =
+            Packet { version: 0, payload: Payload::Add(vec![v1(5), v1(7)]) };
+        let hex = encode(&packet, LengthType::Bits).unwrap();
+        assert_eq!("000058C29870", hex);
+        assert_eq!(packet, parse(hex.parse().unwrap()).unwrap());
+        assert_eq!(vec![packet.clone()], stream(hex.parse().unwrap()).unwrap());
+        let log = format!("D2FE28\n{}\n0000{}\n00", hex, hex);
+        let bits = BitStream::from_hex_reader(Trickle(log.into_bytes()));
+        let both = vec![expected[0].clone(), packet.clone(), packet];
+        assert_eq!(both, stream(bits).unwrap());
+        let bits = "D2FE2800FF".parse().unwrap();
+        assert!(matches!(stream(bits), Err(Error::TrailingGarbage(32))));
+
+        let bits = BitStream::from_hex_reader(Trickle(b"D2FE28FF".to_vec()));
+        assert!(matches!(stream(bits), Err(Error::TrailingGarbage(24))));
+        let bits = "D2FE29D2FE28".parse().unwrap();
+        assert!(matches!(stream(bits), Err(Error::TrailingGarbage(21))));
+        let bits = BitStream::from_hex_reader(Trickle(b"D2FE28G".to_vec()));
+        let mut packets = packets(bits);
+        assert!(matches!(packets.next(), Some(Ok(_))));
+        assert!(matches!(
+            packets.next(),
+            Some(Err(Error::InvalidHexDigit('G')))
+        ));
+        assert!(packets.next().is_none());
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let tree = disassemble("9C0141080250320F1802104A08".parse().unwrap());
+        assert_eq!(
+            "\
+@0 v4 type 7 (eq) length type 0
+  @22 v2 type 0 (sum) length type 1
+    @40 v2 type 4 (literal) 1
+    @51 v4 type 4 (literal) 3
+  @62 v6 type 1 (product) length type 1
+    @80 v0 type 4 (literal) 2
+    @91 v2 type 4 (literal) 2
+",
+            tree.unwrap()
+        );
+    }
+
+    // xorshift, so the generated packets are the same on every run
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    fn random_packet(rng: &mut Rng, depth: usize) -> Packet {
+        let version = rng.next(8);
+        let type_id = if depth == 0 { 4 } else { rng.next(8) };
+        let mut packets = || {
+            let n = 1 + rng.next(4);
+            (0..n).map(|_| random_packet(rng, depth - 1)).collect()
+        };
+        let payload = match type_id {
+            0 => Payload::Add(packets()),
+            1 => Payload::Mul(packets()),
+            2 => Payload::Min(packets()),
+            3 => Payload::Max(packets()),
+            5 => Payload::Gt(packets()),
+            6 => Payload::Lt(packets()),
+            7 => Payload::Eq(packets()),
+            _ => Payload::Literal(rng.next(u64::MAX) >> rng.next(64)),
+        };
+        Packet { version, payload }
+    }
+
+    #[test]
+    fn test_encode() {
+        let packet = Packet { version: 6, payload: Payload::Literal(2021) };
+        assert_eq!("D2FE28", encode(&packet, LengthType::Bits).unwrap());
+        let input = "38006F45291200";
+        let packet = parse(input.parse().unwrap()).unwrap();
+        assert_eq!(input, encode(&packet, LengthType::Bits).unwrap());
+        let input = "EE00D40C823060";
+        let packet = parse(input.parse().unwrap()).unwrap();
+        assert_eq!(input, encode(&packet, LengthType::Count).unwrap());
+
+        let mut rng = Rng(0x2021_1216);
+        for _ in 0..200 {
+            let packet = random_packet(&mut rng, 4);
+            for length_type in [LengthType::Bits, LengthType::Count] {
+                let hex = encode(&packet, length_type).unwrap();
+                assert_eq!(packet, parse(hex.parse().unwrap()).unwrap());
+            }
+        }
+
+        let packet = Packet { version: 8, payload: Payload::Literal(0) };
+        assert!(encode(&packet, LengthType::Bits).is_err());
+    }
+}
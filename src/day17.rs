@@ -0,0 +1,123 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+#[derive(Debug, Clone, Copy)]
+struct Pt(i32, i32);
+
+const MAX_Y_LOL: i32 = 1000;
+
+impl Pt {
+    fn add(&mut self, slope: &Pt) {
+        self.0 += slope.0;
+        self.1 += slope.1;
+    }
+
+    fn drag(&mut self) {
+        // assume x > 0 always
+        if self.0 > 0 {
+            self.0 -= 1;
+        }
+        self.1 -= 1;
+    }
+}
+
+#[derive(Debug)]
+pub struct Rect(Pt, Pt);
+
+impl Rect {
+    fn contains(&self, pt: &Pt) -> bool {
+        self.0 .0 <= pt.0
+            && pt.0 <= self.1 .0
+            && self.0 .1 <= pt.1
+            && pt.1 <= self.1 .1
+    }
+}
+
+fn cannot_reach(target: &Rect, pos: &Pt) -> bool {
+    pos.0 > target.1 .0 || pos.1 < target.0 .1
+}
+
+fn max_y_reaching(target: &Rect, mut slope: Pt) -> Option<i32> {
+    let mut pos = Pt(0, 0);
+    let mut max_y = 0;
+    while !cannot_reach(target, &pos) {
+        pos.add(&slope);
+        slope.drag();
+        max_y = max_y.max(pos.1);
+        if target.contains(&pos) {
+            return Some(max_y);
+        }
+    }
+    None
+}
+
+pub fn part1(target: &Rect) -> i32 {
+    let mut max_y = i32::MIN;
+    for dy in target.0 .1..MAX_Y_LOL {
+        for dx in 1..=target.1 .0 {
+            let slope = Pt(dx, dy);
+            if let Some(y) = max_y_reaching(target, slope) {
+                max_y = max_y.max(y);
+            }
+        }
+    }
+    max_y
+}
+
+pub fn part2(target: &Rect) -> i32 {
+    let mut n = 0;
+    for dy in target.0 .1..MAX_Y_LOL {
+        for dx in 1..=target.1 .0 {
+            let slope = Pt(dx, dy);
+            if max_y_reaching(target, slope).is_some() {
+                n += 1;
+            }
+        }
+    }
+    n
+}
+
+fn parse_range(src: &Source, s: &str, axis: &str) -> Result<(i32, i32)> {
+    let (lo, hi) = src.split_once(src.strip_prefix(s, axis)?, "..")?;
+    Ok((src.parse(lo, "integer")?, src.parse(hi, "integer")?))
+}
+
+pub fn parse(s: &str) -> Result<Rect> {
+    let src = Source::new(s);
+    let s = src.strip_prefix(s.trim(), "target area: ")?;
+    let (x, y) = src.split_once(s, ", ")?;
+    let (x0, x1) = parse_range(&src, x, "x=")?;
+    let (y0, y1) = parse_range(&src, y, "y=")?;
+    Ok(Rect(Pt(x0, y0), Pt(x1, y1)))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = Rect;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(target: &Rect) -> Solved {
+        Ok(part1(target).into())
+    }
+
+    fn part2(target: &Rect) -> Solved {
+        Ok(part2(target).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn test() {
+        assert_eq!(45, part1(&parse(INPUT).unwrap()));
+        assert_eq!(112, part2(&parse(INPUT).unwrap()));
+    }
+}
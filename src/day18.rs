@@ -0,0 +1,574 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+use std::fmt;
+use std::str;
+
+#[derive(Debug, Clone)]
+pub enum Num {
+    Regular(u64),
+    Pair(Box<Num>, Box<Num>),
+}
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Num::Regular(num) => write!(f, "{}", num)?,
+            Num::Pair(lhs, rhs) => write!(f, "[{},{}]", lhs, rhs)?,
+        }
+        Ok(())
+    }
+}
+
+fn add_to_leftmost(num: &mut Num, value: u64) {
+    match num {
+        Num::Regular(num) => *num += value,
+        Num::Pair(lhs, _) => add_to_leftmost(lhs.as_mut(), value),
+    }
+}
+
+fn add_to_rightmost(num: &mut Num, value: u64) {
+    match num {
+        Num::Regular(num) => *num += value,
+        Num::Pair(_, rhs) => add_to_rightmost(rhs.as_mut(), value),
+    }
+}
+
+fn explode(num: &mut Num, level: usize) -> (Option<u64>, Option<u64>, bool) {
+    match num {
+        Num::Regular(_) => (None, None, false),
+
+        Num::Pair(lhs, rhs) if level == 4 => {
+            let (lhs, rhs) = (lhs.as_ref(), rhs.as_ref());
+            if let (Num::Regular(x), Num::Regular(y)) = (lhs, rhs) {
+                let (l, r) = (Some(*x), Some(*y));
+                *num = Num::Regular(0);
+                return (l, r, true);
+            }
+            panic!("invalid exploding pair");
+        }
+
+        Num::Pair(ref mut lhs, ref mut rhs) => {
+            let (l, r, lhs_exploded) = explode(lhs, level + 1);
+            if let Some(r) = r {
+                add_to_leftmost(rhs, r);
+            }
+            if lhs_exploded {
+                return (l, None, true);
+            }
+            let (l, r, rhs_exploded) = explode(rhs, level + 1);
+            if let Some(l) = l {
+                add_to_rightmost(lhs, l);
+            }
+            (None, r, rhs_exploded)
+        }
+    }
+}
+
+fn split(num: &mut Num) -> bool {
+    match num {
+        Num::Regular(value) if *value >= 10 => {
+            *num = Num::Pair(
+                Box::new(Num::Regular(*value / 2)),
+                Box::new(Num::Regular(*value - (*value / 2))),
+            );
+            true
+        }
+        Num::Regular(_) => false,
+        Num::Pair(lhs, rhs) => split(lhs.as_mut()) || split(rhs.as_mut()),
+    }
+}
+
+fn reduce(num: &mut Num) {
+    while explode(num, 0).2 || split(num) {}
+}
+
+pub fn add(lhs: Num, rhs: Num) -> Num {
+    let mut sum = Num::Pair(Box::new(lhs), Box::new(rhs));
+    reduce(&mut sum);
+    sum
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    L,
+    R,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Explode(Vec<Side>, (u64, u64)),
+    Split(Vec<Side>, u64),
+}
+
+fn write_path(f: &mut fmt::Formatter<'_>, path: &[Side]) -> fmt::Result {
+    path.iter().try_for_each(|side| write!(f, "{:?}", side))
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Explode(path, (l, r)) => {
+                write!(f, "explode [{},{}] at ", l, r)?;
+                write_path(f, path)
+            }
+            Action::Split(path, value) => {
+                write!(f, "split {} at ", value)?;
+                write_path(f, path)
+            }
+        }
+    }
+}
+
+// an action and the number it left behind
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub action: Action,
+    pub num: Num,
+}
+
+// an unreduced sum and every step of reducing it
+#[derive(Debug, Clone)]
+pub struct Trace {
+    pub start: Num,
+    pub steps: Vec<Step>,
+}
+
+impl Trace {
+    pub fn result(&self) -> &Num {
+        self.steps.last().map_or(&self.start, |step| &step.num)
+    }
+}
+
+// laid out like the puzzle's worked example; the alternate form also
+// gives the pair or value acted on and its path
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "after addition: {}", self.start)?;
+        for Step { action, num } in &self.steps {
+            let label = match action {
+                Action::Explode(..) => "after explode:  ",
+                Action::Split(..) => "after split:    ",
+            };
+            write!(f, "{}{}", label, num)?;
+            if f.alternate() {
+                write!(f, "  ({})", action)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// the path to the leftmost part of num matching pred, given the depth
+fn find(
+    num: &Num,
+    path: &mut Vec<Side>,
+    pred: &impl Fn(&Num, usize) -> bool,
+) -> bool {
+    if pred(num, path.len()) {
+        return true;
+    }
+    if let Num::Pair(lhs, rhs) = num {
+        for (side, num) in [(Side::L, lhs), (Side::R, rhs)] {
+            path.push(side);
+            if find(num, path, pred) {
+                return true;
+            }
+            path.pop();
+        }
+    }
+    false
+}
+
+fn at<'a>(num: &'a Num, path: &[Side]) -> &'a Num {
+    match (num, path) {
+        (Num::Pair(lhs, _), [Side::L, rest @ ..]) => at(lhs, rest),
+        (Num::Pair(_, rhs), [Side::R, rest @ ..]) => at(rhs, rest),
+        _ => num,
+    }
+}
+
+// reduces num like reduce, recording each explode and split
+pub fn reduce_traced(num: &mut Num) -> Vec<Step> {
+    let mut steps = Vec::new();
+    loop {
+        let mut path = Vec::new();
+        let action = if find(num, &mut path, &|num, depth| {
+            depth == 4 && matches!(num, Num::Pair(..))
+        }) {
+            let Num::Pair(lhs, rhs) = at(num, &path) else {
+                unreachable!()
+            };
+            let pair = match (lhs.as_ref(), rhs.as_ref()) {
+                (Num::Regular(l), Num::Regular(r)) => (*l, *r),
+                _ => panic!("invalid exploding pair"),
+            };
+            explode(num, 0);
+            Action::Explode(path, pair)
+        } else if find(
+            num,
+            &mut path,
+            &|num, _| matches!(num, Num::Regular(value) if *value >= 10),
+        ) {
+            let Num::Regular(value) = *at(num, &path) else {
+                unreachable!()
+            };
+            split(num);
+            Action::Split(path, value)
+        } else {
+            return steps;
+        };
+        steps.push(Step { action, num: num.clone() });
+    }
+}
+
+pub fn add_traced(lhs: Num, rhs: Num) -> Trace {
+    let start = Num::Pair(Box::new(lhs), Box::new(rhs));
+    let mut sum = start.clone();
+    let steps = reduce_traced(&mut sum);
+    Trace { start, steps }
+}
+
+// a number as its regular values, left to right, each with the count of
+// pairs enclosing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatNum(Vec<(u64, usize)>);
+
+fn flatten(num: &Num, depth: usize, out: &mut Vec<(u64, usize)>) {
+    match num {
+        Num::Regular(value) => out.push((*value, depth)),
+        Num::Pair(lhs, rhs) => {
+            flatten(lhs, depth + 1, out);
+            flatten(rhs, depth + 1, out);
+        }
+    }
+}
+
+impl From<&Num> for FlatNum {
+    fn from(num: &Num) -> Self {
+        let mut values = Vec::new();
+        flatten(num, 0, &mut values);
+        FlatNum(values)
+    }
+}
+
+impl FlatNum {
+    // reduced numbers nest at most four deep, so the first value deeper
+    // than that starts a pair of two regular numbers
+    fn explode(&mut self) -> bool {
+        let values = &mut self.0;
+        let Some(i) = values.iter().position(|&(_, depth)| depth > 4) else {
+            return false;
+        };
+        let ((l, depth), (r, _)) = (values[i], values[i + 1]);
+        if i > 0 {
+            values[i - 1].0 += l;
+        }
+        if let Some((value, _)) = values.get_mut(i + 2) {
+            *value += r;
+        }
+        values.splice(i..i + 2, [(0, depth - 1)]);
+        true
+    }
+
+    fn split(&mut self) -> bool {
+        let values = &mut self.0;
+        let Some(i) = values.iter().position(|&(value, _)| value >= 10) else {
+            return false;
+        };
+        let (value, depth) = values[i];
+        let halves = [(value / 2, depth + 1), (value - value / 2, depth + 1)];
+        values.splice(i..i + 1, halves);
+        true
+    }
+
+    pub fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    pub fn add(&self, rhs: &FlatNum) -> FlatNum {
+        let values = self.0.iter().chain(&rhs.0);
+        let mut sum =
+            FlatNum(values.map(|&(value, depth)| (value, depth + 1)).collect());
+        sum.reduce();
+        sum
+    }
+
+    pub fn magnitude(&self) -> u64 {
+        // the top two values share a depth only when they form a pair
+        let mut stack: Vec<(u64, usize)> = Vec::new();
+        for &(mut value, mut depth) in &self.0 {
+            while let Some(&(lhs, d)) = stack.last() {
+                if d != depth {
+                    break;
+                }
+                stack.pop();
+                value = 3 * lhs + 2 * value;
+                depth -= 1;
+            }
+            stack.push((value, depth));
+        }
+        stack[0].0
+    }
+
+    fn write(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        i: &mut usize,
+        depth: usize,
+    ) -> fmt::Result {
+        let (value, d) = self.0[*i];
+        if d == depth {
+            *i += 1;
+            return write!(f, "{}", value);
+        }
+        write!(f, "[")?;
+        self.write(f, i, depth + 1)?;
+        write!(f, ",")?;
+        self.write(f, i, depth + 1)?;
+        write!(f, "]")
+    }
+}
+
+impl fmt::Display for FlatNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &mut 0, 0)
+    }
+}
+
+// nums must not be empty, which parse_nums makes sure of
+pub fn sum(nums: &[Num]) -> FlatNum {
+    let mut nums = nums.iter().map(FlatNum::from);
+    let first = nums.next().unwrap();
+    nums.fold(first, |sum, num| sum.add(&num))
+}
+
+pub fn magnitude(num: Num) -> u64 {
+    match num {
+        Num::Regular(value) => value,
+        Num::Pair(lhs, rhs) => 3 * magnitude(*lhs) + 2 * magnitude(*rhs),
+    }
+}
+
+fn eat(src: &Source, s: &mut &str, ch: char) -> Result<()> {
+    *s = s.strip_prefix(ch).ok_or_else(|| src.error(s, format!("'{}'", ch)))?;
+    Ok(())
+}
+
+fn parse(src: &Source, s: &mut &str) -> Result<Num> {
+    if s.starts_with('[') {
+        eat(src, s, '[')?;
+        let lhs = parse(src, s)?;
+        eat(src, s, ',')?;
+        let rhs = parse(src, s)?;
+        eat(src, s, ']')?;
+        Ok(Num::Pair(Box::new(lhs), Box::new(rhs)))
+    } else {
+        let digit = s.chars().next().and_then(|ch| ch.to_digit(10));
+        let digit = digit.ok_or_else(|| src.error(s, "digit or '['"))?;
+        *s = &s[1..];
+        Ok(Num::Regular(digit as u64))
+    }
+}
+
+fn parse_line(src: &Source, line: &str) -> Result<Num> {
+    let mut s = line.trim();
+    let num = parse(src, &mut s)?;
+    if !s.is_empty() {
+        return Err(src.error(s, "end of line"));
+    }
+    Ok(num)
+}
+
+pub fn parse_nums(s: &str) -> Result<Vec<Num>> {
+    let src = Source::new(s);
+    let nums = s.lines().map(|line| parse_line(&src, line));
+    let nums = nums.collect::<Result<Vec<Num>>>()?;
+    if nums.is_empty() {
+        return Err(src.eof("snailfish number"));
+    }
+    Ok(nums)
+}
+
+pub fn part2(nums: &[Num]) -> u64 {
+    let nums: Vec<FlatNum> = nums.iter().map(FlatNum::from).collect();
+    let mut max = u64::MIN;
+    for i in 0..nums.len() {
+        for j in 0..nums.len() {
+            if i != j {
+                let m = nums[i].add(&nums[j]).magnitude();
+                if m > max {
+                    max = m;
+                }
+            }
+        }
+    }
+    max
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<Num>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse_nums(s)
+    }
+
+    fn part1(nums: &Vec<Num>) -> Solved {
+        Ok(sum(nums).magnitude().into())
+    }
+
+    fn part2(nums: &Vec<Num>) -> Solved {
+        Ok(part2(nums).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_num(s: &str) -> Result<Num> {
+        parse_line(&Source::new(s), s)
+    }
+
+    static INPUT: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+    [[[5,[2,8]],4],[5,[[9,9],0]]]
+    [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+    [[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+    [[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+    [[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+    [[[[5,4],[7,7]],8],[[8,3],8]]
+    [[9,3],[[9,9],[6,[4,9]]]]
+    [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+    [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    #[test]
+    fn test_sum() {
+        let nums = parse_nums(
+            "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+             [7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+             [[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+             [[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+             [7,[5,[[3,8],[1,4]]]]
+             [[2,[2,2]],[8,[8,1]]]
+             [2,9]
+             [1,[[[9,3],9],[[9,0],[0,7]]]]
+             [[[5,[7,4]],7],1]
+             [[[[4,2],2],6],[8,7]]",
+        )
+        .unwrap();
+        let expected =
+            parse_num("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+                .unwrap();
+        assert_eq!(expected.to_string(), sum(&nums).to_string());
+    }
+
+    #[test]
+    fn test_explode() {
+        for (before, after, pair) in &[
+            (
+                "[[[[[9,8],1],2],3],4]",
+                "[[[[0,9],2],3],4]",
+                (Some(9), None),
+            ),
+            (
+                "[7,[6,[5,[4,[3,2]]]]]",
+                "[7,[6,[5,[7,0]]]]",
+                (None, Some(2)),
+            ),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]", (None, None)),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                (None, None),
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+                (None, Some(2)),
+            ),
+        ] {
+            let mut result = parse_num(before).unwrap();
+            let (l, r, exploded) = explode(&mut result, 0);
+            assert!(exploded);
+            assert_eq!(pair, &(l, r));
+            assert_eq!(
+                parse_num(after).unwrap().to_string(),
+                result.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let lhs = parse_num("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let rhs = parse_num("[1,1]").unwrap();
+        let trace = add_traced(lhs.clone(), rhs.clone());
+        assert_eq!(
+            "\
+after addition: [[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]
+after explode:  [[[[0,7],4],[7,[[8,4],9]]],[1,1]]
+after explode:  [[[[0,7],4],[15,[0,13]]],[1,1]]
+after split:    [[[[0,7],4],[[7,8],[0,13]]],[1,1]]
+after split:    [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]
+after explode:  [[[[0,7],4],[[7,8],[6,0]]],[8,1]]
+",
+            trace.to_string()
+        );
+        let actions: Vec<String> =
+            trace.steps.iter().map(|step| step.action.to_string()).collect();
+        assert_eq!(
+            vec![
+                "explode [4,3] at LLLL",
+                "explode [8,4] at LRRL",
+                "split 15 at LRL",
+                "split 13 at LRRR",
+                "explode [6,7] at LRRR",
+            ],
+            actions
+        );
+        assert_eq!(add(lhs, rhs).to_string(), trace.result().to_string());
+        assert!(format!("{:#}", trace).ends_with(
+            "after explode:  [[[[0,7],4],[[7,8],[6,0]]],[8,1]]  \
+             (explode [6,7] at LRRR)\n"
+        ));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(4140, sum(&parse_nums(INPUT).unwrap()).magnitude());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(3993, part2(&parse_nums(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_flat() {
+        let nums = parse_nums(INPUT).unwrap();
+        for a in &nums {
+            assert_eq!(a.to_string(), FlatNum::from(a).to_string());
+            for b in &nums {
+                let tree = add(a.clone(), b.clone());
+                let flat = FlatNum::from(a).add(&FlatNum::from(b));
+                assert_eq!(FlatNum::from(&tree), flat);
+                assert_eq!(tree.to_string(), flat.to_string());
+                assert_eq!(magnitude(tree), flat.magnitude());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_nums("[1,2]\n[[1,2],3").unwrap_err();
+        assert_eq!((2, 9), (err.line, err.col));
+        assert_eq!("']'", err.expected);
+        let err = parse_nums("[1,x]").unwrap_err();
+        assert_eq!((1, 4), (err.line, err.col));
+        assert_eq!("x]", err.found);
+        let err = parse_nums("").unwrap_err();
+        assert_eq!("snailfish number", err.expected);
+    }
+}
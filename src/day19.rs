@@ -0,0 +1,506 @@
+use crate::parse::{Result, Source};
+use crate::pt::{manhattan3, Pt3};
+use crate::solution::{self, Solution, Solved};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+type Pts = HashSet<Pt3>;
+
+#[derive(Clone)]
+pub struct Scan {
+    pub beacons: Pts,
+    pub scanners: Pts,
+}
+
+fn parse_pt(src: &Source, s: &str) -> Result<Pt3> {
+    let (x, rest) = src.split_once(s, ",")?;
+    let (y, z) = src.split_once(rest, ",")?;
+    let (x, y) = (src.parse(x, "integer")?, src.parse(y, "integer")?);
+    Ok((x, y, src.parse(z, "integer")?))
+}
+
+fn parse_scan(src: &Source, s: &str) -> Result<Scan> {
+    let mut lines = s.lines();
+    let header = lines.next().ok_or_else(|| src.error(s, "scanner header"))?;
+    src.strip_prefix(header, "--- scanner ")?;
+    let beacons =
+        lines.map(|line| parse_pt(src, line)).collect::<Result<_>>()?;
+    let scanners = Pts::from([(0, 0, 0)]);
+    Ok(Scan { beacons, scanners })
+}
+
+pub fn parse(s: &str) -> Result<Vec<Scan>> {
+    let src = Source::new(s);
+    s.split("\n\n").map(|s| parse_scan(&src, s)).collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    // beacons two scans must have in common to be aligned
+    pub threshold: usize,
+    // Manhattan distance within which two beacons count as the same
+    pub tolerance: i64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { threshold: 12, tolerance: 0 }
+    }
+}
+
+// sorted squared distances between every pair of beacons, which don't
+// change under rotation or translation
+type Fingerprint = Vec<i64>;
+
+fn fingerprint(beacons: &[Pt3]) -> Fingerprint {
+    let mut print = Fingerprint::new();
+    for (i, &(x1, y1, z1)) in beacons.iter().enumerate() {
+        for &(x2, y2, z2) in &beacons[i + 1..] {
+            let (dx, dy, dz) = (x1 - x2, y1 - y2, z1 - z2);
+            print.push(dx * dx + dy * dy + dz * dz);
+        }
+    }
+    print.sort();
+    print
+}
+
+// counts distances in both prints, matching greedily. moving each beacon
+// by up to tolerance changes each distance by at most twice that.
+fn shared(print1: &Fingerprint, print2: &Fingerprint, tolerance: i64) -> usize {
+    let slack = 2.0 * tolerance as f64;
+    let (mut i, mut j, mut n) = (0, 0, 0);
+    while i < print1.len() && j < print2.len() {
+        let (d1, d2) = ((print1[i] as f64).sqrt(), (print2[j] as f64).sqrt());
+        if (d1 - d2).abs() <= slack {
+            (i, j, n) = (i + 1, j + 1, n + 1);
+        } else if d1 < d2 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    n
+}
+
+pub type Rot = [[i64; 3]; 3];
+
+fn det(m: &Rot) -> i64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+// the 24 signed permutation matrices that preserve handedness
+fn rotations() -> Vec<Rot> {
+    let perms = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut rots = vec![];
+    for perm in perms {
+        for signs in 0..8 {
+            let mut m = [[0; 3]; 3];
+            for (row, &col) in perm.iter().enumerate() {
+                m[row][col] = if signs >> row & 1 == 1 { -1 } else { 1 };
+            }
+            if det(&m) == 1 {
+                rots.push(m);
+            }
+        }
+    }
+    rots
+}
+
+fn rotate(m: &Rot, (x, y, z): Pt3) -> Pt3 {
+    let row = |r: [i64; 3]| r[0] * x + r[1] * y + r[2] * z;
+    (row(m[0]), row(m[1]), row(m[2]))
+}
+
+fn near(fixed: &Pts, pt: Pt3, tolerance: i64) -> bool {
+    tolerance == 0 && fixed.contains(&pt)
+        || tolerance > 0
+            && fixed.iter().any(|&f| manhattan3(f, pt) <= tolerance)
+}
+
+// every offset within Manhattan distance r of d
+fn ball((dx, dy, dz): Pt3, r: i64) -> impl Iterator<Item = Pt3> {
+    (-r..=r).flat_map(move |x| {
+        let r = r - x.abs();
+        (-r..=r).flat_map(move |y| {
+            let r = r - y.abs();
+            (-r..=r).map(move |z| (dx + x, dy + y, dz + z))
+        })
+    })
+}
+
+// the translation that lines up at least threshold of pts with fixed, if
+// any. candidates are tried in order of how many exact matches they make.
+fn find_offset(fixed: &Pts, pts: &[Pt3], config: &Config) -> Option<Pt3> {
+    let mut votes = HashMap::new();
+    for &(x1, y1, z1) in fixed {
+        for &(x2, y2, z2) in pts {
+            *votes.entry((x1 - x2, y1 - y2, z1 - z2)).or_insert(0) += 1;
+        }
+    }
+    if config.tolerance == 0 {
+        let votes = votes.into_iter().filter(|&(_, n)| n >= config.threshold);
+        return votes.max_by_key(|&(d, n)| (n, d)).map(|(d, _)| d);
+    }
+    let mut votes: Vec<(usize, Pt3)> =
+        votes.into_iter().map(|(d, n)| (n, d)).collect();
+    votes.sort_by(|a, b| b.cmp(a));
+    let matches = |(dx, dy, dz): Pt3, tolerance: i64| {
+        let pts = pts.iter().filter(|&&(x, y, z)| {
+            near(fixed, (x + dx, y + dy, z + dz), tolerance)
+        });
+        pts.count()
+    };
+    // each candidate comes from a single noisy pair, so it can itself be
+    // off by the tolerance: check it with twice the slack, then settle on
+    // the best offset within the tolerance of it
+    votes.into_iter().find_map(|(_, d)| {
+        if matches(d, 2 * config.tolerance) < config.threshold {
+            return None;
+        }
+        let best = ball(d, config.tolerance)
+            .map(|d| (matches(d, config.tolerance), d))
+            .max()?;
+        (best.0 >= config.threshold).then_some(best.1)
+    })
+}
+
+// where a scanner is relative to scanner 0, and which scanner it was
+// aligned against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub pos: Pt3,
+    pub rot: Rot,
+    pub parent: Option<usize>,
+}
+
+impl Placement {
+    pub fn transform(&self, pt: Pt3) -> Pt3 {
+        let (x, y, z) = rotate(&self.rot, pt);
+        (x + self.pos.0, y + self.pos.1, z + self.pos.2)
+    }
+}
+
+// places every scan reachable from scan 0 in its frame, breadth-first so
+// the result doesn't depend on hash order
+pub fn locate(scans: &[Scan], config: &Config) -> Vec<Option<Placement>> {
+    let beacons: Vec<Vec<Pt3>> = scans
+        .iter()
+        .map(|scan| {
+            let mut beacons: Vec<Pt3> = scan.beacons.iter().copied().collect();
+            beacons.sort();
+            beacons
+        })
+        .collect();
+    let prints: Vec<Fingerprint> =
+        beacons.iter().map(|b| fingerprint(b)).collect();
+    let rots = rotations();
+    let pairs = config.threshold * config.threshold.saturating_sub(1) / 2;
+    let mut placements = vec![None; scans.len()];
+    let mut fixed = vec![Pts::new(); scans.len()];
+    placements[0] =
+        Some(Placement { pos: (0, 0, 0), rot: rots[0], parent: None });
+    fixed[0] = scans[0].beacons.clone();
+    let mut q = VecDeque::from([0]);
+    while let Some(i) = q.pop_front() {
+        for j in 0..scans.len() {
+            if placements[j].is_some()
+                || shared(&prints[i], &prints[j], config.tolerance) < pairs
+            {
+                continue;
+            }
+            for &rot in &rots {
+                let pts: Vec<Pt3> =
+                    beacons[j].iter().map(|&pt| rotate(&rot, pt)).collect();
+                if let Some(pos) = find_offset(&fixed[i], &pts, config) {
+                    let placement = Placement { pos, rot, parent: Some(i) };
+                    fixed[j] = beacons[j]
+                        .iter()
+                        .map(|&pt| placement.transform(pt))
+                        .collect();
+                    placements[j] = Some(placement);
+                    q.push_back(j);
+                    break;
+                }
+            }
+        }
+    }
+    placements
+}
+
+// merges the beacons of every placed scan, keeping only the first seen of
+// any that are within tolerance of each other
+pub fn align(scans: &[Scan], config: &Config) -> Scan {
+    let (mut beacons, mut scanners) = (Pts::new(), Pts::new());
+    for (scan, placement) in scans.iter().zip(locate(scans, config)) {
+        if let Some(placement) = placement {
+            let mut pts: Vec<Pt3> = scan.beacons.iter().copied().collect();
+            pts.sort();
+            for pt in pts {
+                let pt = placement.transform(pt);
+                if !near(&beacons, pt, config.tolerance) {
+                    beacons.insert(pt);
+                }
+            }
+            scanners.insert(placement.pos);
+        }
+    }
+    Scan { beacons, scanners }
+}
+
+pub fn max_dist(pts: &Pts) -> i64 {
+    let mut max = i64::MIN;
+    for pt1 in pts {
+        for pt2 in pts {
+            if pt1 != pt2 {
+                max = max.max(manhattan3(*pt1, *pt2));
+            }
+        }
+    }
+    max
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = Vec<Scan>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(scans: &Vec<Scan>) -> Solved {
+        Ok(align(scans, &Config::default()).beacons.len().into())
+    }
+
+    fn part2(scans: &Vec<Scan>) -> Solved {
+        Ok(max_dist(&align(scans, &Config::default()).scanners).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    #[test]
+    fn test_part1() {
+        let scans = parse(INPUT).unwrap();
+        let scan = align(&scans, &Config::default());
+        assert_eq!(79, scan.beacons.len());
+        assert_eq!(3621, max_dist(&scan.scanners));
+    }
+
+    #[test]
+    fn test_locate() {
+        let scans = parse(INPUT).unwrap();
+        let placements: Vec<Placement> =
+            locate(&scans, &Config::default()).into_iter().flatten().collect();
+        let pos: Vec<Pt3> = placements.iter().map(|p| p.pos).collect();
+        assert_eq!(
+            vec![
+                (0, 0, 0),
+                (68, -1246, -43),
+                (1105, -1205, 1229),
+                (-92, -2380, -20),
+                (-20, -1133, 1061)
+            ],
+            pos
+        );
+        let parents: Vec<_> = placements.iter().map(|p| p.parent).collect();
+        assert_eq!(vec![None, Some(0), Some(4), Some(1), Some(1)], parents);
+        assert_eq!(
+            (-618, -824, -621),
+            placements[1].transform((686, 422, 578))
+        );
+        assert_eq!([[-1, 0, 0], [0, 1, 0], [0, 0, -1]], placements[1].rot);
+    }
+
+    #[test]
+    fn test_config() {
+        let scans = parse(INPUT).unwrap();
+        let strict = Config { threshold: 13, tolerance: 0 };
+        assert_eq!(1, locate(&scans, &strict).iter().flatten().count());
+        let loose = Config { threshold: 3, tolerance: 0 };
+        assert_eq!(5, locate(&scans, &loose).iter().flatten().count());
+
+        // jitter every beacon by one unit, so no two readings of a beacon
+        // seen by two scanners need agree exactly
+        let noisy: Vec<Scan> = scans
+            .iter()
+            .map(|scan| {
+                let mut pts: Vec<Pt3> = scan.beacons.iter().copied().collect();
+                pts.sort();
+                let jitter = |(i, (x, y, z)): (usize, Pt3)| {
+                    (x + if i % 2 == 0 { 1 } else { -1 }, y, z)
+                };
+                let beacons = pts.into_iter().enumerate().map(jitter).collect();
+                Scan { beacons, scanners: scan.scanners.clone() }
+            })
+            .collect();
+        let config = Config { threshold: 12, tolerance: 2 };
+        let scan = align(&noisy, &config);
+        assert_eq!(5, scan.scanners.len());
+        assert_eq!(79, scan.beacons.len());
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rots = rotations();
+        let pts: Pts = rots.iter().map(|rot| rotate(rot, (1, 2, 3))).collect();
+        assert_eq!(24, pts.len());
+        assert_eq!((1, 2, 3), rotate(&rots[0], (1, 2, 3)));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let print1 = fingerprint(&[(0, 0, 0), (1, 2, 3), (4, 0, 0)]);
+        let print2 = fingerprint(&[(5, 5, 5), (5, 5, 9), (7, 6, 4)]);
+        assert_eq!(3, print1.len());
+        assert_eq!(1, shared(&print1, &print2, 0));
+        assert_eq!(3, shared(&print1, &print2, 2));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+pub fn parse(s: &str) -> Result<Vec<Command>> {
+    let src = Source::new(s);
+    s.lines().map(|line| parse_command(&src, line)).collect()
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+pub trait Commandable {
+    fn apply(self, cmd: &Command) -> Self;
+    fn result(&self) -> i32;
+}
+
+pub fn apply(t: impl Commandable, cmds: &[Command]) -> i32 {
+    cmds.iter().fold(t, |acc, cmd| acc.apply(cmd)).result()
+}
+
+#[derive(Default)]
+pub struct Location {
+    pos: i32,
+    depth: i32,
+}
+
+impl Commandable for Location {
+    fn apply(mut self, cmd: &Command) -> Self {
+        match *cmd {
+            Command::Forward(dist) => self.pos += dist,
+            Command::Down(dist) => self.depth += dist,
+            Command::Up(dist) => self.depth -= dist,
+        };
+        self
+    }
+
+    fn result(&self) -> i32 {
+        self.pos * self.depth
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    loc: Location,
+    aim: i32,
+}
+
+impl Commandable for State {
+    fn apply(mut self, cmd: &Command) -> Self {
+        match *cmd {
+            Command::Forward(dist) => {
+                self.loc.pos += dist;
+                self.loc.depth += dist * self.aim;
+            }
+            Command::Down(dist) => self.aim += dist,
+            Command::Up(dist) => self.aim -= dist,
+        };
+        self
+    }
+
+    fn result(&self) -> i32 {
+        self.loc.result()
+    }
+}
+
+fn parse_command(src: &Source, s: &str) -> Result<Command> {
+    let (dir, dist) = src.split_once(s, " ")?;
+    let dist = src.parse(dist, "integer")?;
+    match dir {
+        "forward" => Ok(Command::Forward(dist)),
+        "down" => Ok(Command::Down(dist)),
+        "up" => Ok(Command::Up(dist)),
+        _ => Err(src.error(dir, "forward, down or up")),
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Command>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(cmds: &Vec<Command>) -> Solved {
+        Ok(apply(Location::default(), cmds).into())
+    }
+
+    fn part2(cmds: &Vec<Command>) -> Solved {
+        Ok(apply(State::default(), cmds).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    fn cmds() -> Vec<Command> {
+        parse(INPUT).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        let init = Location { pos: 0, depth: 0 };
+        let result = apply(init, &cmds());
+        assert_eq!(150, result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let init = Location { pos: 0, depth: 0 };
+        let result = apply(State { loc: init, aim: 0 }, &cmds());
+        assert_eq!(900, result);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("forward 5\nsideways 3").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.col));
+        assert_eq!("forward, down or up", err.expected);
+        let err = parse("down x").unwrap_err();
+        assert_eq!((1, 6), (err.line, err.col));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::grid::Grid;
+use crate::parse::{Result, Source};
+use crate::pt::Pt;
+use crate::solution::{self, Solution, Solved};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bit {
+    On,
+    Off,
+}
+
+impl Bit {
+    fn from_char(ch: char) -> Option<Bit> {
+        match ch {
+            '.' => Some(Bit::Off),
+            '#' => Some(Bit::On),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitMap {
+    default: Bit,
+    bits: Grid<Bit>,
+}
+
+fn parse_bits<'a>(
+    src: &'a Source,
+    line: &'a str,
+) -> impl Iterator<Item = Result<Bit>> + 'a {
+    line.char_indices().map(|(i, ch)| {
+        Bit::from_char(ch).ok_or_else(|| src.error(&line[i..], "'.' or '#'"))
+    })
+}
+
+pub fn parse(s: &str) -> Result<(Vec<Bit>, BitMap)> {
+    let src = Source::new(s);
+    let (fst, snd) = s
+        .split_once("\n\n")
+        .ok_or_else(|| src.eof("blank line before image"))?;
+    let alg = parse_bits(&src, fst.trim()).collect::<Result<Vec<Bit>>>()?;
+    if alg.len() != 512 {
+        return Err(src.error(fst.trim(), "512 bit algorithm"));
+    }
+    let bits = Grid::parse_with(&src, snd.trim(), Bit::from_char, "pixel")?;
+    Ok((alg, BitMap { default: Bit::Off, bits }))
+}
+
+static NBRS: &[Pt] = &[
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 0),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+fn nbrs_of((r, c): Pt) -> impl Iterator<Item = Pt> {
+    NBRS.iter().map(move |(dr, dc)| (r + dr, c + dc))
+}
+
+fn bits_to_idx(bits: impl Iterator<Item = Bit>) -> usize {
+    bits.fold(0, |acc, bit| match bit {
+        Bit::Off => acc << 1,
+        Bit::On => (acc << 1) | 1,
+    })
+}
+
+fn next_default(alg: &[Bit], map: &BitMap) -> Bit {
+    let bits = (0..9).map(|_| &map.default).copied();
+    let idx = bits_to_idx(bits);
+    alg[idx]
+}
+
+fn get_idx(map: &BitMap, pt: Pt) -> usize {
+    let bits = nbrs_of(pt).map(|pt| map.bits.get(pt).unwrap_or(&map.default));
+    bits_to_idx(bits.copied())
+}
+
+// the image grows by one pixel on every side, since those are the only
+// pixels outside it that can differ from the default
+fn apply(alg: &[Bit], map: &BitMap) -> BitMap {
+    let (width, height) = (map.bits.width() + 2, map.bits.height() + 2);
+    let mut bits = Grid::new(width, height, Bit::Off);
+    for (r, c) in bits.pts().collect::<Vec<_>>() {
+        bits[(r, c)] = alg[get_idx(map, (r - 1, c - 1))];
+    }
+    BitMap { default: next_default(alg, map), bits }
+}
+
+pub fn apply_n(alg: &[Bit], img: BitMap, n: usize) -> BitMap {
+    (0..n).fold(img, |acc, _| apply(alg, &acc))
+}
+
+pub fn on_bits(img: &BitMap) -> usize {
+    img.bits.values().filter(|&&bit| bit == Bit::On).count()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = (Vec<Bit>, BitMap);
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1((alg, img): &(Vec<Bit>, BitMap)) -> Solved {
+        Ok(on_bits(&apply_n(alg, img.clone(), 2)).into())
+    }
+
+    fn part2((alg, img): &(Vec<Bit>, BitMap)) -> Solved {
+        Ok(on_bits(&apply_n(alg, img.clone(), 50)).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn test_part1() {
+        let (alg, img) = parse(INPUT).unwrap();
+        let img = apply(&alg, &img);
+        let img = apply(&alg, &img);
+        assert_eq!(
+            35,
+            img.bits.values().filter(|&&bit| bit == Bit::On).count()
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use crate::parse::{Result, Source};
+use crate::search;
+use crate::solution::{self, Answer, Solution, Solved};
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Amphipod {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl fmt::Debug for Amphipod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ch = match *self {
+            Amphipod::A => 'A',
+            Amphipod::B => 'B',
+            Amphipod::C => 'C',
+            Amphipod::D => 'D',
+        };
+        write!(f, "{}", ch)
+    }
+}
+
+impl Amphipod {
+    fn step_cost(self) -> i32 {
+        match self {
+            Amphipod::A => 1,
+            Amphipod::B => 10,
+            Amphipod::C => 100,
+            Amphipod::D => 1000,
+        }
+    }
+
+    fn room(self) -> u8 {
+        match self {
+            Amphipod::A => 0,
+            Amphipod::B => 1,
+            Amphipod::C => 2,
+            Amphipod::D => 3,
+        }
+    }
+
+    fn belongs_in_room(self, room: u8) -> bool {
+        self.room() == room
+    }
+}
+
+impl Amphipod {
+    fn from_char(ch: char) -> Option<Amphipod> {
+        match ch {
+            'A' => Some(Amphipod::A),
+            'B' => Some(Amphipod::B),
+            'C' => Some(Amphipod::C),
+            'D' => Some(Amphipod::D),
+            _ => None,
+        }
+    }
+}
+
+pub type Tile = Option<Amphipod>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Pos {
+    Hallway(u8),
+    Room(u8, u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Burrow {
+    pub pods: Vec<Pos>,
+    pub hallway: Vec<Tile>,
+    pub rooms: Vec<Vec<Tile>>,
+}
+
+impl Burrow {
+    pub fn at(&self, pos: Pos) -> Tile {
+        match pos {
+            Pos::Hallway(i) => self.hallway[i as usize],
+            Pos::Room(i, j) => self.rooms[i as usize][j as usize],
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        let all_are = |pod, room: &[Tile]| room.iter().all(|&t| t == Some(pod));
+        all_are(Amphipod::A, &self.rooms[0])
+            && all_are(Amphipod::B, &self.rooms[1])
+            && all_are(Amphipod::C, &self.rooms[2])
+            && all_are(Amphipod::D, &self.rooms[3])
+    }
+
+    fn occupied(&self) -> Vec<Pos> {
+        let hallway = (0..self.hallway.len()).map(|i| Pos::Hallway(i as u8));
+        let rooms = self.rooms.iter().enumerate().flat_map(|(i, room)| {
+            (0..room.len()).map(move |j| Pos::Room(i as u8, j as u8))
+        });
+        let mut pods: Vec<Pos> = hallway
+            .chain(rooms)
+            .filter(|&pos| self.at(pos).is_some())
+            .collect();
+        pods.sort();
+        pods
+    }
+
+    fn set(&mut self, pos: Pos, tile: Tile) {
+        match pos {
+            Pos::Hallway(i) => self.hallway[i as usize] = tile,
+            Pos::Room(i, j) => self.rooms[i as usize][j as usize] = tile,
+        }
+    }
+
+    pub fn moved(&self, from: Pos, to: Pos) -> Burrow {
+        let mut burrow = self.clone();
+        burrow.set(to, self.at(from));
+        burrow.set(from, None);
+        for pos in burrow.pods.iter_mut().filter(|pos| **pos == from) {
+            *pos = to;
+        }
+        burrow.pods.sort();
+        burrow
+    }
+
+    // a pod is home if it's in its own room with only its own kind below it
+    fn is_home(&self, pos: Pos) -> bool {
+        match (pos, self.at(pos)) {
+            (Pos::Room(room, depth), Some(pod))
+                if pod.belongs_in_room(room) =>
+            {
+                self.rooms[room as usize][depth as usize..]
+                    .iter()
+                    .all(|&tile| tile == Some(pod))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn unfold(&self) -> Burrow {
+        use Amphipod::*;
+        let extra = [[D, D], [C, B], [B, A], [A, C]];
+        let mut burrow = self.clone();
+        for (room, pods) in burrow.rooms.iter_mut().zip(extra) {
+            room.splice(1..1, pods.map(Some));
+        }
+        burrow.pods = burrow.occupied();
+        burrow
+    }
+}
+
+pub fn parse(s: &str) -> Result<Burrow> {
+    let src = Source::new(s);
+    let lines: Vec<&str> = s.lines().collect();
+    let mut rooms = vec![vec![None; 2]; 4];
+    let mut pods = vec![];
+    let mut counts = [0; 4];
+    for (room, tiles) in rooms.iter_mut().enumerate() {
+        for (depth, tile) in tiles.iter_mut().enumerate() {
+            let col = 3 + room * 2;
+            let line =
+                lines.get(2 + depth).ok_or_else(|| src.eof("amphipod A-D"))?;
+            let at = &line[col.min(line.len())..];
+            let pod = at.chars().next().and_then(Amphipod::from_char);
+            let pod = pod.ok_or_else(|| src.error(at, "amphipod A-D"))?;
+            // with eight places, a third of one kind means too few of another
+            let count = &mut counts[pod.room() as usize];
+            if *count == 2 {
+                let expected = format!("amphipod other than {:?}", pod);
+                return Err(src.error(at, expected));
+            }
+            *count += 1;
+            *tile = Some(pod);
+            pods.push(Pos::Room(room as u8, depth as u8));
+        }
+    }
+    let hallway = vec![None; 11];
+    Ok(Burrow { hallway, rooms, pods })
+}
+
+fn can_visit(burrow: &Burrow, pos: Pos) -> bool {
+    burrow.at(pos).is_none()
+}
+
+fn can_stop(burrow: &Burrow, from: Pos, pos: Pos, pod: Amphipod) -> bool {
+    match pos {
+        // pods only leave the hallway to go home, and never block a room
+        Pos::Hallway(i) => {
+            matches!(from, Pos::Room(_, _))
+                && (!(2..=8).contains(&i) || (i % 2 != 0))
+        }
+        Pos::Room(room, depth) => {
+            pod.belongs_in_room(room)
+                && burrow.rooms[room as usize][depth as usize + 1..]
+                    .iter()
+                    .all(|&tile| tile == Some(pod))
+        }
+    }
+}
+
+fn nbrs(burrow: &Burrow, pos: Pos) -> [Option<Pos>; 3] {
+    let mut nbrs = [None; 3];
+    let mut j = 0;
+    let mut push = |pos| {
+        nbrs[j] = Some(pos);
+        j += 1;
+    };
+
+    match pos {
+        Pos::Hallway(i) => {
+            if i > 0 {
+                push(Pos::Hallway(i - 1));
+            }
+            if (i as usize) < burrow.hallway.len() - 1 {
+                push(Pos::Hallway(i + 1));
+            }
+            if (2..9).contains(&i) && (i % 2 == 0) {
+                let room = (i - 2) / 2;
+                push(Pos::Room(room, 0));
+            }
+        }
+
+        Pos::Room(room, depth) => {
+            if depth == 0 {
+                push(Pos::Hallway(room * 2 + 2));
+            } else {
+                push(Pos::Room(room, depth - 1));
+            }
+            if (depth as usize) < burrow.rooms[room as usize].len() - 1 {
+                push(Pos::Room(room, depth + 1));
+            }
+        }
+    }
+
+    nbrs
+}
+
+pub fn destinations(burrow: &Burrow, from: Pos) -> Option<Vec<(Pos, i32)>> {
+    let pod = burrow.at(from)?;
+    let mut destinations = vec![];
+    if burrow.is_home(from) {
+        return Some(destinations);
+    }
+
+    // bfs
+    let mut q = VecDeque::new();
+    q.push_back((from, 0));
+    let mut v = BTreeSet::new();
+    v.insert(from);
+
+    while let Some((pos, cost)) = q.pop_front() {
+        if pos != from && can_stop(burrow, from, pos, pod) {
+            destinations.push((pos, cost));
+        }
+        for nbr in nbrs(burrow, pos).into_iter().flatten() {
+            if !v.contains(&nbr) && can_visit(burrow, nbr) {
+                v.insert(nbr);
+                q.push_back((nbr, cost + pod.step_cost()));
+            }
+        }
+    }
+
+    Some(destinations)
+}
+
+fn coords(pos: Pos) -> (i32, i32) {
+    match pos {
+        Pos::Hallway(i) => (0, i as i32),
+        Pos::Room(room, depth) => (depth as i32 + 1, room as i32 * 2 + 2),
+    }
+}
+
+// steps between two positions, ignoring other pods
+fn steps(from: Pos, to: Pos) -> i32 {
+    let ((r1, c1), (r2, c2)) = (coords(from), coords(to));
+    if c1 == c2 {
+        (r1 - r2).abs()
+    } else {
+        r1 + r2 + (c1 - c2).abs()
+    }
+}
+
+// every pod outside its room has at least to walk to the room's door
+fn min_remaining(burrow: &Burrow) -> i64 {
+    let pods = burrow.pods.iter().map(|&pos| (pos, burrow.at(pos).unwrap()));
+    let energy = pods.map(|(pos, pod)| match pos {
+        Pos::Room(room, _) if pod.belongs_in_room(room) => 0,
+        _ => steps(pos, Pos::Room(pod.room(), 0)) * pod.step_cost(),
+    });
+    energy.map(i64::from).sum()
+}
+
+// each burrow one move away, with the energy the move takes
+fn moves(burrow: &Burrow) -> Vec<(Burrow, i64)> {
+    let moves = burrow.pods.iter().flat_map(|&from| {
+        let dests = destinations(burrow, from).unwrap_or_default();
+        dests
+            .into_iter()
+            .map(move |(to, cost)| (burrow.moved(from, to), cost as i64))
+    });
+    moves.collect()
+}
+
+pub fn min_energy(burrow: Burrow) -> Option<i64> {
+    let path = search::astar(burrow, Burrow::is_done, moves, min_remaining);
+    path.map(|(energy, _)| energy)
+}
+
+pub fn part1(burrow: &Burrow) -> Option<i64> {
+    min_energy(burrow.clone())
+}
+
+pub fn part2(burrow: &Burrow) -> Option<i64> {
+    min_energy(burrow.unfold())
+}
+
+pub const NO_SOLUTION: &str = "no way to organize the amphipods";
+
+fn solved(energy: Option<i64>) -> Solved {
+    let why = || solution::Error::Solve(NO_SOLUTION.to_string());
+    energy.map(Answer::from).ok_or_else(why)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Burrow;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(burrow: &Burrow) -> Solved {
+        solved(part1(burrow))
+    }
+
+    fn part2(burrow: &Burrow) -> Solved {
+        solved(part2(burrow))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn test_destinations() {
+        let burrow = parse(INPUT).unwrap();
+        let dests = destinations(&burrow, Pos::Room(2, 0)).unwrap();
+        assert_eq!(7, dests.len());
+        assert!(dests.contains(&(Pos::Hallway(5), 20)));
+        assert_eq!(Some(vec![]), destinations(&burrow, Pos::Room(0, 1)));
+        assert_eq!(None, destinations(&burrow, Pos::Hallway(0)));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let burrow = parse(INPUT).unwrap().unfold();
+        let room: Vec<Tile> = burrow.rooms[3].clone();
+        use Amphipod::*;
+        assert_eq!(vec![Some(D), Some(A), Some(C), Some(A)], room);
+        assert_eq!(16, burrow.pods.len());
+    }
+
+    #[test]
+    fn test_energy() {
+        let burrow = parse(INPUT).unwrap();
+        let moved = burrow.moved(Pos::Room(2, 0), Pos::Hallway(3));
+        assert!(moves(&burrow).contains(&(moved, 40)));
+        assert!(min_remaining(&burrow) <= 12521);
+        let done = parse(
+            &INPUT.replace("B#C#B#D", "A#B#C#D").replace("A#D#C#A", "A#B#C#D"),
+        )
+        .unwrap();
+        assert_eq!(0, min_remaining(&done));
+        let moved = done.moved(Pos::Room(0, 0), Pos::Hallway(0));
+        assert_eq!(3, min_remaining(&moved));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Some(12521), part1(&parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Some(44169), part2(&parse(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("###B#C#B#D###", "###B#C#A#D###");
+        let err = parse(&input).unwrap_err();
+        assert_eq!((4, 10), (err.line, err.col));
+        assert_eq!("amphipod other than A", err.expected);
+        let err = parse(&INPUT.replace('D', "E")).unwrap_err();
+        assert_eq!("amphipod A-D", err.expected);
+    }
+}
@@ -0,0 +1,483 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Answer, Solution, Solved};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Inp,
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+static OPCODES: &[(&str, Opcode)] = &[
+    ("inp", Opcode::Inp),
+    ("add", Opcode::Add),
+    ("mul", Opcode::Mul),
+    ("div", Opcode::Div),
+    ("mod", Opcode::Mod),
+    ("eql", Opcode::Eql),
+];
+
+impl Opcode {
+    fn name(self) -> &'static str {
+        OPCODES.iter().find(|&&(_, op)| op == self).unwrap().0
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Opcode::Inp => "inp",
+            Opcode::Add => "+",
+            Opcode::Mul => "*",
+            Opcode::Div => "/",
+            Opcode::Mod => "%",
+            Opcode::Eql => "==",
+        }
+    }
+}
+
+pub type Reg = usize;
+pub type Regs = [i64; 4];
+
+fn reg_name(reg: Reg) -> char {
+    (b'w' + reg as u8) as char
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    Reg(Reg),
+    Lit(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instr {
+    pub op: Opcode,
+    pub dest: Reg,
+    pub arg: Option<Arg>,
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.op.name(), reg_name(self.dest))?;
+        match self.arg {
+            Some(Arg::Reg(reg)) => write!(f, " {}", reg_name(reg)),
+            Some(Arg::Lit(val)) => write!(f, " {}", val),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_reg(src: &Source, s: &str) -> Result<Reg> {
+    match s {
+        "w" | "x" | "y" | "z" => Ok((s.as_bytes()[0] - b'w') as Reg),
+        _ => Err(src.error(s, "register w-z")),
+    }
+}
+
+fn parse_arg(src: &Source, s: &str) -> Result<Arg> {
+    match s.parse() {
+        Ok(val) => Ok(Arg::Lit(val)),
+        Err(_) => parse_reg(src, s).map(Arg::Reg),
+    }
+}
+
+fn parse_instr(src: &Source, line: &str) -> Result<Instr> {
+    let end = &line[line.len()..];
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or(end);
+    let op = OPCODES.iter().find(|&&(op, _)| op == name);
+    let op = op.ok_or_else(|| src.error(name, "opcode"))?.1;
+    let dest = parse_reg(src, words.next().unwrap_or(end))?;
+    let arg = match op {
+        Opcode::Inp => None,
+        _ => Some(parse_arg(src, words.next().unwrap_or(end))?),
+    };
+    if let Some(word) = words.next() {
+        return Err(src.error(word, "end of line"));
+    }
+    Ok(Instr { op, dest, arg })
+}
+
+pub fn parse(s: &str) -> Result<Vec<Instr>> {
+    let src = Source::new(s);
+    let lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+    lines.map(|line| parse_instr(&src, line)).collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    NoInput,
+    DivByZero,
+    BadMod,
+    Overflow,
+}
+
+fn apply(op: Opcode, a: i64, b: i64) -> std::result::Result<i64, Error> {
+    match op {
+        Opcode::Inp => unreachable!(),
+        Opcode::Add => a.checked_add(b).ok_or(Error::Overflow),
+        Opcode::Mul => a.checked_mul(b).ok_or(Error::Overflow),
+        Opcode::Div if b == 0 => Err(Error::DivByZero),
+        Opcode::Div => Ok(a / b),
+        Opcode::Mod if a < 0 || b <= 0 => Err(Error::BadMod),
+        Opcode::Mod => Ok(a % b),
+        Opcode::Eql => Ok((a == b) as i64),
+    }
+}
+
+pub struct Alu<'a> {
+    prog: &'a [Instr],
+    inputs: &'a [i64],
+    pc: usize,
+    inp: usize,
+    pub regs: Regs,
+}
+
+impl<'a> Alu<'a> {
+    pub fn new(prog: &'a [Instr], inputs: &'a [i64]) -> Self {
+        Alu { prog, inputs, pc: 0, inp: 0, regs: [0; 4] }
+    }
+
+    pub fn done(&self) -> bool {
+        self.pc >= self.prog.len()
+    }
+
+    pub fn step(&mut self) -> std::result::Result<(), Error> {
+        let instr = self.prog[self.pc];
+        self.pc += 1;
+        let val = match instr.arg {
+            None => {
+                let val = self.inputs.get(self.inp).ok_or(Error::NoInput)?;
+                self.inp += 1;
+                *val
+            }
+            Some(arg) => {
+                let b = match arg {
+                    Arg::Reg(reg) => self.regs[reg],
+                    Arg::Lit(val) => val,
+                };
+                apply(instr.op, self.regs[instr.dest], b)?
+            }
+        };
+        self.regs[instr.dest] = val;
+        Ok(())
+    }
+}
+
+pub fn run(prog: &[Instr], inputs: &[i64]) -> std::result::Result<Regs, Error> {
+    let mut alu = Alu::new(prog, inputs);
+    while !alu.done() {
+        alu.step()?;
+    }
+    Ok(alu.regs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Lit(i64),
+    Reg(Reg),
+    Inp(usize),
+    Bin(Opcode, Box<Expr>, Box<Expr>),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Lit(val) => write!(f, "{}", val),
+            Expr::Reg(reg) => write!(f, "{}", reg_name(*reg)),
+            Expr::Inp(i) => write!(f, "input{}", i),
+            Expr::Bin(op, lhs, rhs) => {
+                write!(f, "({}{}{})", lhs, op.symbol(), rhs)
+            }
+        }
+    }
+}
+
+impl Expr {
+    // Bounds on the value, assuming registers carried in from earlier code
+    // are non-negative and inputs are digits.
+    fn range(&self) -> (i64, i64) {
+        match self {
+            Expr::Lit(val) => (*val, *val),
+            Expr::Reg(_) => (0, i64::MAX),
+            Expr::Inp(_) => (1, 9),
+            Expr::Bin(op, lhs, rhs) => {
+                let ((l0, l1), (r0, r1)) = (lhs.range(), rhs.range());
+                let corners = |f: fn(i64, i64) -> i64| {
+                    let xs = [f(l0, r0), f(l0, r1), f(l1, r0), f(l1, r1)];
+                    (*xs.iter().min().unwrap(), *xs.iter().max().unwrap())
+                };
+                match (op, rhs.as_ref()) {
+                    (Opcode::Add, _) => corners(i64::saturating_add),
+                    (Opcode::Mul, _) => corners(i64::saturating_mul),
+                    (Opcode::Div, Expr::Lit(d)) if *d != 0 => {
+                        corners(i64::saturating_div)
+                    }
+                    (Opcode::Mod, Expr::Lit(m)) if *m > 0 && l0 >= 0 => {
+                        (0, l1.min(m - 1))
+                    }
+                    (Opcode::Eql, _) => (0, 1),
+                    _ => (i64::MIN, i64::MAX),
+                }
+            }
+        }
+    }
+
+    pub fn bin(op: Opcode, lhs: Expr, rhs: Expr) -> Expr {
+        let ((l0, l1), (r0, r1)) = (lhs.range(), rhs.range());
+        let lit = |e: &Expr| match *e {
+            Expr::Lit(val) => Some(val),
+            _ => None,
+        };
+        let (a, b) = (lit(&lhs), lit(&rhs));
+        if let (Some(a), Some(b)) = (a, b) {
+            if let Ok(val) = apply(op, a, b) {
+                return Expr::Lit(val);
+            }
+        }
+        match (op, a, b) {
+            (Opcode::Add, Some(0), _) => rhs,
+            (Opcode::Add, _, Some(0)) => lhs,
+            (Opcode::Mul, Some(0), _) | (Opcode::Mul, _, Some(0)) => {
+                Expr::Lit(0)
+            }
+            (Opcode::Mul, Some(1), _) => rhs,
+            (Opcode::Mul | Opcode::Div, _, Some(1)) => lhs,
+            (Opcode::Div, _, Some(d)) if d > 0 && l0 >= 0 && l1 < d => {
+                Expr::Lit(0)
+            }
+            (Opcode::Mod, _, Some(m)) if l0 >= 0 && l1 < m => lhs,
+            (Opcode::Eql, _, _) if l1 < r0 || r1 < l0 => Expr::Lit(0),
+            _ => Expr::Bin(op, Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    pub fn eval(
+        &self,
+        regs: &Regs,
+        inputs: &[i64],
+    ) -> std::result::Result<i64, Error> {
+        match self {
+            Expr::Lit(val) => Ok(*val),
+            Expr::Reg(reg) => Ok(regs[*reg]),
+            Expr::Inp(i) => inputs.get(*i).copied().ok_or(Error::NoInput),
+            Expr::Bin(op, lhs, rhs) => {
+                apply(*op, lhs.eval(regs, inputs)?, rhs.eval(regs, inputs)?)
+            }
+        }
+    }
+}
+
+pub fn decompile(prog: &[Instr], mut regs: [Expr; 4]) -> [Expr; 4] {
+    let mut inputs = 0;
+    for instr in prog {
+        regs[instr.dest] = match instr.arg {
+            None => {
+                inputs += 1;
+                Expr::Inp(inputs - 1)
+            }
+            Some(arg) => {
+                let rhs = match arg {
+                    Arg::Reg(reg) => regs[reg].clone(),
+                    Arg::Lit(val) => Expr::Lit(val),
+                };
+                Expr::bin(instr.op, regs[instr.dest].clone(), rhs)
+            }
+        };
+    }
+    regs
+}
+
+// MONAD is fourteen blocks, one per digit, each either pushing the digit
+// plus an offset onto z as a base-26 stack, or popping the top and
+// comparing it plus an offset to the digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    Push(i64),
+    Pop(i64),
+}
+
+fn find_cmp(e: &Expr) -> Option<&Expr> {
+    match e {
+        Expr::Bin(Opcode::Eql, lhs, rhs) if **rhs == Expr::Inp(0) => Some(lhs),
+        Expr::Bin(_, lhs, rhs) => find_cmp(lhs).or_else(|| find_cmp(rhs)),
+        _ => None,
+    }
+}
+
+fn analyze(block: &[Instr]) -> Option<Block> {
+    let regs = [Expr::Reg(0), Expr::Reg(1), Expr::Reg(2), Expr::Reg(3)];
+    let [_, _, _, z] = decompile(block, regs);
+    match find_cmp(&z) {
+        None => z.eval(&[0; 4], &[0]).ok().map(Block::Push),
+        Some(lhs) => lhs.eval(&[0; 4], &[0]).ok().map(Block::Pop),
+    }
+}
+
+// one block per digit of the fourteen-digit model number, each starting
+// with its inp
+pub fn blocks(prog: &[Instr]) -> Option<Vec<Block>> {
+    let starts: Vec<usize> = (0..prog.len())
+        .filter(|&i| prog[i].op == Opcode::Inp)
+        .chain([prog.len()])
+        .collect();
+    if starts[0] != 0 || starts.len() != 15 {
+        return None;
+    }
+    starts.windows(2).map(|w| analyze(&prog[w[0]..w[1]])).collect()
+}
+
+pub fn model_number(prog: &[Instr], largest: bool) -> Option<i64> {
+    let blocks = blocks(prog)?;
+    let mut digits = vec![0; blocks.len()];
+    let mut stack = vec![];
+    for (j, block) in blocks.into_iter().enumerate() {
+        match block {
+            Block::Push(b) => stack.push((j, b)),
+            Block::Pop(a) => {
+                let (i, b) = stack.pop()?;
+                let d = b + a;
+                let wi = if largest { 9.min(9 - d) } else { 1.max(1 - d) };
+                if !(1..=9).contains(&wi) || !(1..=9).contains(&(wi + d)) {
+                    return None;
+                }
+                digits[i] = wi;
+                digits[j] = wi + d;
+            }
+        }
+    }
+    if !stack.is_empty() || run(prog, &digits).ok()?[3] != 0 {
+        return None;
+    }
+    Some(digits.iter().fold(0, |n, d| n * 10 + d))
+}
+
+pub fn part1(prog: &[Instr]) -> Option<i64> {
+    model_number(prog, true)
+}
+
+pub fn part2(prog: &[Instr]) -> Option<i64> {
+    model_number(prog, false)
+}
+
+pub const NO_MODEL_NUMBER: &str = "no valid model number";
+
+fn solved(n: Option<i64>) -> Solved {
+    let why = || solution::Error::Solve(NO_MODEL_NUMBER.to_string());
+    n.map(Answer::from).ok_or_else(why)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Vec<Instr>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(prog: &Vec<Instr>) -> Solved {
+        solved(part1(prog))
+    }
+
+    fn part2(prog: &Vec<Instr>) -> Solved {
+        solved(part2(prog))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn monad(blocks: &[(i64, i64, i64)]) -> String {
+        let mut prog = String::new();
+        for (div, a, b) in blocks {
+            prog += &format!(
+                "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\n\
+                 eql x w\neql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\n\
+                 mul z y\nmul y 0\nadd y w\nadd y {}\nmul y x\nadd z y\n",
+                div, a, b
+            );
+        }
+        prog
+    }
+
+    #[test]
+    fn test_run() {
+        let prog = parse("inp x\nmul x -1").unwrap();
+        assert_eq!(Ok([0, -7, 0, 0]), run(&prog, &[7]));
+        let prog = parse("inp z\ninp x\nmul z 3\neql z x").unwrap();
+        assert_eq!(0, run(&prog, &[8, 3]).unwrap()[3]);
+        assert_eq!(1, run(&prog, &[3, 9]).unwrap()[3]);
+        assert_eq!(Err(Error::NoInput), run(&prog, &[3]));
+        let prog = parse(
+            "inp w
+            add z w
+            mod z 2
+            div w 2
+            add y w
+            mod y 2
+            div w 2
+            add x w
+            mod x 2
+            div w 2
+            mod w 2",
+        )
+        .unwrap();
+        assert_eq!(Ok([1, 0, 1, 0]), run(&prog, &[26]));
+        assert_eq!("div w 2", prog[3].to_string());
+    }
+
+    #[test]
+    fn test_decompile() {
+        let prog = parse("inp x\nmul y 0\nadd y x\nmul y 3\neql y z").unwrap();
+        let regs = [Expr::Lit(0), Expr::Lit(0), Expr::Lit(0), Expr::Lit(0)];
+        let [_, x, y, _] = decompile(&prog, regs);
+        assert_eq!("input0", x.to_string());
+        assert_eq!(Expr::Lit(0), y);
+        let prog = parse(&monad(&[(1, 12, 4)])).unwrap();
+        let regs = [Expr::Reg(0), Expr::Reg(1), Expr::Reg(2), Expr::Reg(3)];
+        let [_, _, _, z] = decompile(&prog, regs);
+        assert_eq!("((z*26)+(input0+4))", z.to_string());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("inp w\nadd q 1").unwrap_err();
+        assert_eq!((2, 5), (err.line, err.col));
+        let err = parse("inp w\nsub x 1").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.col));
+        let err = parse("add x").unwrap_err();
+        assert_eq!("", err.found);
+    }
+
+    #[test]
+    fn test_model_number() {
+        let prog = parse(&monad(&[
+            (1, 13, 12),
+            (1, 12, 7),
+            (1, 10, 8),
+            (1, 15, 8),
+            (1, 11, 15),
+            (26, -16, 12),
+            (1, 10, 8),
+            (26, -11, 13),
+            (26, -13, 3),
+            (1, 13, 13),
+            (26, -8, 3),
+            (26, -1, 9),
+            (26, -4, 4),
+            (26, -14, 13),
+        ]))
+        .unwrap();
+        assert_eq!(Some(96299896449997), part1(&prog));
+        assert_eq!(Some(31162141116841), part2(&prog));
+        assert_eq!(None, model_number(&prog[..18 * 13], true));
+        assert_eq!(None, model_number(&[], true));
+        assert_eq!(None, model_number(&parse("add x 1").unwrap(), true));
+        let err = solution::Error::Solve(NO_MODEL_NUMBER.to_string());
+        assert_eq!(Err(err), Day24::part2(&prog[..18 * 13].to_vec()));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::grid::Grid;
+use crate::parse::{Result, Source};
+use crate::solution::{self, Answer, Solution, Solved};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    East,
+    South,
+    Empty,
+}
+
+impl Tile {
+    fn from_char(ch: char) -> Option<Tile> {
+        match ch {
+            '>' => Some(Tile::East),
+            'v' => Some(Tile::South),
+            '.' => Some(Tile::Empty),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Tile::East => '>',
+            Tile::South => 'v',
+            Tile::Empty => '.',
+        }
+    }
+}
+
+pub struct Floor<'a>(pub &'a Grid<Tile>);
+
+impl fmt::Display for Floor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grid = self.0;
+        for r in 0..grid.height() as i32 {
+            for c in 0..grid.width() as i32 {
+                write!(f, "{}", grid[(r, c)].to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse(s: &str) -> Result<Grid<Tile>> {
+    Grid::parse_with(&Source::new(s), s, Tile::from_char, "tile")
+}
+
+fn step_herd(
+    grid: &Grid<Tile>,
+    herd: Tile,
+    (dr, dc): (i32, i32),
+) -> Grid<Tile> {
+    let mut next = grid.clone();
+    for (pt, &tile) in grid.iter() {
+        let nbr = grid.wrap((pt.0 + dr, pt.1 + dc));
+        if tile == herd && grid[nbr] == Tile::Empty {
+            next[nbr] = herd;
+            next[pt] = Tile::Empty;
+        }
+    }
+    next
+}
+
+pub fn step(grid: &Grid<Tile>) -> Grid<Tile> {
+    step_herd(&step_herd(grid, Tile::East, (0, 1)), Tile::South, (1, 0))
+}
+
+pub fn settle(
+    grid: &Grid<Tile>,
+    mut dump: impl FnMut(usize, &Grid<Tile>),
+) -> usize {
+    let mut grid = grid.clone();
+    for i in 1.. {
+        let next = step(&grid);
+        dump(i, &next);
+        if next == grid {
+            return i;
+        }
+        grid = next;
+    }
+    unreachable!()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Grid<Tile>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(grid: &Grid<Tile>) -> Solved {
+        Ok(settle(grid, |_, _| {}).into())
+    }
+
+    // there's no second puzzle on the last day
+    fn part2(_: &Grid<Tile>) -> Solved {
+        Ok(Answer::Text("Merry Christmas!".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn test_step() {
+        let grid = parse(
+            "...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..",
+        )
+        .unwrap();
+        let want =
+            ">......\n..v....\n..>.v..\n.>.v...\n...>...\n.......\nv......\n";
+        let mut grid = grid;
+        for _ in 0..4 {
+            grid = step(&grid);
+        }
+        assert_eq!(want, Floor(&grid).to_string());
+    }
+
+    #[test]
+    fn test_part1() {
+        let grid = parse(INPUT).unwrap();
+        let mut dumped = 0;
+        assert_eq!(58, settle(&grid, |_, _| dumped += 1));
+        assert_eq!(58, dumped);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("..>\n.x.").unwrap_err();
+        assert_eq!((2, 2), (err.line, err.col));
+        let err = parse("..>\n..").unwrap_err();
+        assert_eq!("3 tiles", err.expected);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+pub fn parse(s: &str) -> Result<Vec<&str>> {
+    let src = Source::new(s);
+    let rows: Vec<&str> = s.lines().collect();
+    let width = rows.first().ok_or_else(|| src.eof("binary number"))?.len();
+    for row in &rows {
+        if let Some(i) = row.find(|ch| ch != '0' && ch != '1') {
+            return Err(src.error(&row[i..], "0 or 1"));
+        } else if row.len() != width {
+            return Err(src.error(row, format!("{} bits", width)));
+        }
+    }
+    Ok(rows)
+}
+
+pub fn part1(rows: &[&str]) -> u64 {
+    let s = (0..rows[0].len()).map(|i| match most_common(i, rows) {
+        MostCommon::One | MostCommon::Tie => '1',
+        MostCommon::Zero => '0',
+    });
+    let gamma = u64::from_str_radix(&s.collect::<String>(), 2).unwrap();
+    let epsilon = !gamma & ((1 << rows[0].len()) - 1);
+    gamma * epsilon
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum MostCommon {
+    Tie,
+    Zero,
+    One,
+}
+
+fn most_common(i: usize, rows: &[&str]) -> MostCommon {
+    let ones = rows.iter().filter(|row| row.as_bytes()[i] == b'1').count();
+    if ones == rows.len() - ones {
+        MostCommon::Tie
+    } else if ones < rows.len() - ones {
+        MostCommon::Zero
+    } else {
+        MostCommon::One
+    }
+}
+
+pub fn part2(rows: &[&str]) -> u64 {
+    let s = find_row(rows.into(), |mcb, bit| match mcb {
+        MostCommon::Tie | MostCommon::One => bit == b'1',
+        MostCommon::Zero => bit == b'0',
+    });
+    let t = find_row(rows.into(), |mcb, bit| match mcb {
+        MostCommon::Tie | MostCommon::One => bit == b'0',
+        MostCommon::Zero => bit == b'1',
+    });
+    let oxy = u64::from_str_radix(s, 2).unwrap();
+    let co2 = u64::from_str_radix(t, 2).unwrap();
+    oxy * co2
+}
+
+fn find_row(
+    mut rows: Vec<&str>,
+    bit_pred: impl Fn(MostCommon, u8) -> bool,
+) -> &str {
+    for i in 0..rows[0].len() {
+        if rows.len() == 1 {
+            break;
+        }
+        let mcb = most_common(i, &rows);
+        rows.retain(|row| bit_pred(mcb, row.as_bytes()[i]));
+    }
+    rows[0]
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(rows: &Vec<&'_ str>) -> Solved {
+        Ok(part1(rows).into())
+    }
+
+    fn part2(rows: &Vec<&'_ str>) -> Solved {
+        Ok(part2(rows).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn test_part1() {
+        let rows = INPUT.lines().collect::<Vec<&str>>();
+        assert_eq!(198, part1(&rows));
+    }
+
+    #[test]
+    fn test_part2() {
+        let rows = INPUT.lines().collect::<Vec<&str>>();
+        assert_eq!(230, part2(&rows));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+use std::collections::{HashMap, HashSet};
+
+const ROWS: usize = 5;
+const COLS: usize = 5;
+
+type Idx = (usize, usize);
+
+#[derive(Clone)]
+pub struct Board {
+    tiles: HashMap<Idx, i32>,
+    indices: HashMap<i32, Vec<Idx>>,
+    marked: HashSet<Idx>,
+    bingo: bool,
+}
+
+impl Board {
+    fn sum_unmarked(&self) -> i32 {
+        let val_of = |(idx, &n)| if self.marked.contains(idx) { 0 } else { n };
+        self.tiles.iter().map(val_of).sum()
+    }
+
+    fn has_bingo(&self, (i, j): Idx) -> bool {
+        (0..ROWS).all(|j| self.marked.contains(&(i, j)))
+            || (0..COLS).all(|i| self.marked.contains(&(i, j)))
+    }
+
+    fn play(&mut self, value: i32) {
+        if let Some(indices) = self.indices.get(&value) {
+            self.marked.extend(indices.iter());
+            if indices.iter().any(|idx| self.has_bingo(*idx)) {
+                self.bingo = true;
+            }
+        }
+    }
+}
+
+pub fn play_all(order: Vec<i32>, mut boards: Vec<Board>) -> Vec<i32> {
+    let mut results = Vec::new();
+    let mut removed = HashSet::new();
+    for value in order {
+        for (i, board) in boards.iter_mut().enumerate() {
+            if removed.contains(&i) {
+                continue;
+            }
+            board.play(value);
+            if board.bingo {
+                results.push(value * board.sum_unmarked());
+                removed.insert(i);
+            }
+        }
+    }
+    results
+}
+
+fn indices_table(tiles: &HashMap<Idx, i32>) -> HashMap<i32, Vec<Idx>> {
+    tiles.iter().fold(HashMap::new(), |mut acc, (&idx, &val)| {
+        acc.entry(val).or_insert(Vec::new()).push(idx);
+        acc
+    })
+}
+
+fn parse_board(src: &Source, s: &str) -> Result<Board> {
+    let nums = s
+        .split_whitespace()
+        .map(|n| src.parse(n, "integer"))
+        .collect::<Result<Vec<i32>>>()?;
+    if nums.len() != ROWS * COLS {
+        return Err(src.error(s.trim(), format!("{}x{} board", ROWS, COLS)));
+    }
+    let to_tile = |(i, n)| ((i / COLS, i % COLS), n);
+    let tiles = nums.into_iter().enumerate().map(to_tile).collect();
+    let indices = indices_table(&tiles);
+    Ok(Board { tiles, indices, bingo: false, marked: HashSet::new() })
+}
+
+pub fn parse(s: &str) -> Result<(Vec<i32>, Vec<Board>)> {
+    let src = Source::new(s);
+    let mut segs = s.split("\n\n");
+    let order = segs.next().unwrap().split(',');
+    let order =
+        order.map(|n| src.parse(n, "integer")).collect::<Result<_>>()?;
+    let boards =
+        segs.map(|seg| parse_board(&src, seg)).collect::<Result<_>>()?;
+    Ok((order, boards))
+}
+
+fn no_winner() -> solution::Error {
+    solution::Error::Solve("no board wins".to_string())
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = (Vec<i32>, Vec<Board>);
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1((order, boards): &(Vec<i32>, Vec<Board>)) -> Solved {
+        let results = play_all(order.clone(), boards.clone());
+        results.first().map(|&score| score.into()).ok_or_else(no_winner)
+    }
+
+    fn part2((order, boards): &(Vec<i32>, Vec<Board>)) -> Solved {
+        let results = play_all(order.clone(), boards.clone());
+        results.last().map(|&score| score.into()).ok_or_else(no_winner)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str =
+        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+    22 13 17 11  0
+     8  2 23  4 24
+    21  9 14 16  7
+     6 10  3 18  5
+     1 12 20 15 19
+
+     3 15  0  2 22
+     9 18 13 17  5
+    19  8  7 25 23
+    20 11 10 24  4
+    14 21 16 12  6
+
+    14 21 17 24  4
+    10 16 15  9 19
+    18  8 23 26 20
+    22 11 13  6  5
+     2  0 12  3  7";
+
+    #[test]
+    fn test_part1() {
+        let (order, boards) = parse(INPUT).unwrap();
+        let results = play_all(order, boards);
+        assert_eq!(4512, results[0]);
+    }
+
+    #[test]
+    fn test_part2() {
+        let (order, boards) = parse(INPUT).unwrap();
+        let results = play_all(order, boards);
+        assert_eq!(1924, *results.last().unwrap());
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let input = Day4::parse("1,2,3").unwrap();
+        assert_eq!(Err(no_winner()), Day4::part1(&input));
+        assert_eq!(Err(no_winner()), Day4::part2(&input));
+    }
+}
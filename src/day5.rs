@@ -0,0 +1,95 @@
+use crate::parse::{Result, Source};
+use crate::pt::Pt;
+use crate::solution::{self, Solution, Solved};
+
+pub type Line = (Pt, Pt);
+type Grid = std::collections::HashMap<Pt, i32>;
+
+fn slope(l: &Line) -> Pt {
+    let dx = (l.1 .0 - l.0 .0).signum();
+    let dy = (l.1 .1 - l.0 .1).signum();
+    (dx, dy)
+}
+
+fn steps(l: &Line) -> i32 {
+    (l.1 .0 - l.0 .0).abs().max((l.1 .1 - l.0 .1).abs())
+}
+
+fn apply_line(g: Grid, l: &Line) -> Grid {
+    let d = slope(l);
+    (0..=steps(l)).fold(g, |mut g, i| {
+        let pt = (l.0 .0 + i * d.0, l.0 .1 + i * d.1);
+        *g.entry(pt).or_default() += 1;
+        g
+    })
+}
+
+pub fn not_diag(l: &&Line) -> bool {
+    l.0 .1 == l.1 .1 || l.0 .0 == l.1 .0
+}
+
+pub fn intersections(lines: &[Line], filter: impl Fn(&&Line) -> bool) -> usize {
+    let grid = lines.iter().filter(filter).fold(Grid::new(), apply_line);
+    grid.values().filter(|&&count| count > 1).count()
+}
+
+fn parse_pt(src: &Source, s: &str) -> Result<Pt> {
+    let (x, y) = src.split_once(s, ",")?;
+    Ok((src.parse(x, "integer")?, src.parse(y, "integer")?))
+}
+
+fn parse_line(src: &Source, s: &str) -> Result<Line> {
+    let (from, to) = src.split_once(s, " -> ")?;
+    Ok((parse_pt(src, from)?, parse_pt(src, to)?))
+}
+
+pub fn parse_lines(s: &str) -> Result<Vec<Line>> {
+    let src = Source::new(s);
+    s.lines().map(|line| parse_line(&src, line)).collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Vec<Line>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse_lines(s)
+    }
+
+    fn part1(lines: &Vec<Line>) -> Solved {
+        Ok(intersections(lines, not_diag).into())
+    }
+
+    fn part2(lines: &Vec<Line>) -> Solved {
+        Ok(intersections(lines, |_| true).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn test_part1() {
+        let lines = parse_lines(INPUT).unwrap();
+        assert_eq!(5, intersections(&lines, not_diag));
+    }
+
+    #[test]
+    fn test_part2() {
+        let lines = parse_lines(INPUT).unwrap();
+        assert_eq!(12, intersections(&lines, |_| true));
+    }
+}
@@ -0,0 +1,67 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+pub fn parse(s: &str) -> Result<Vec<i64>> {
+    let src = Source::new(s);
+    let mut fish = vec![0; 9];
+    for timer in s.trim().split(',') {
+        let i: usize = src.parse(timer, "timer")?;
+        let count = fish.get_mut(i);
+        *count.ok_or_else(|| src.error(timer, "timer from 0 to 8"))? += 1;
+    }
+    Ok(fish)
+}
+
+fn step(fish: &mut [i64], next_fish: &mut [i64]) {
+    next_fish[8] = fish[0];
+    next_fish[..8].copy_from_slice(&fish[1..=8]);
+    next_fish[6] += fish[0];
+    fish.copy_from_slice(next_fish);
+}
+
+pub fn simulate(mut fish: Vec<i64>, steps: usize) -> i64 {
+    let mut next_fish = vec![0; fish.len()];
+    for _ in 0..steps {
+        step(&mut fish, &mut next_fish);
+    }
+    fish.iter().sum::<i64>()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = Vec<i64>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(fish: &Vec<i64>) -> Solved {
+        Ok(simulate(fish.clone(), 80).into())
+    }
+
+    fn part2(fish: &Vec<i64>) -> Solved {
+        Ok(simulate(fish.clone(), 256).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_part1() {
+        let fish = parse(INPUT).unwrap();
+        let result = simulate(fish, 80);
+        assert_eq!(5934, result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let fish = parse(INPUT).unwrap();
+        let result = simulate(fish, 256);
+        assert_eq!(26984457539, result);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::parse::{Result, Source};
+use crate::solution::{self, Solution, Solved};
+
+pub fn parse(s: &str) -> Result<Vec<i32>> {
+    let src = Source::new(s);
+    s.trim().split(',').map(|x| src.parse(x, "integer")).collect()
+}
+
+fn min_dist_sum(xs: &[i32], f: impl Fn(i32) -> i32) -> i32 {
+    let min = *xs.iter().min().unwrap();
+    let max = *xs.iter().max().unwrap();
+    let dist = |x: i32, y: i32| f((x - y).abs());
+    let dist_sum = |to: i32| xs.iter().map(|&x| dist(x, to)).sum();
+    (min..=max).map(dist_sum).min().unwrap()
+}
+
+pub fn part1(xs: &[i32]) -> i32 {
+    min_dist_sum(xs, |n| n)
+}
+
+pub fn part2(xs: &[i32]) -> i32 {
+    min_dist_sum(xs, |n| (n * (n + 1)) / 2)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(xs: &Vec<i32>) -> Solved {
+        Ok(part1(xs).into())
+    }
+
+    fn part2(xs: &Vec<i32>) -> Solved {
+        Ok(part2(xs).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_part1() {
+        let xs: Vec<i32> = parse(INPUT).unwrap();
+        assert_eq!(37, part1(&xs));
+    }
+
+    #[test]
+    fn test_part2() {
+        let xs: Vec<i32> = parse(INPUT).unwrap();
+        assert_eq!(168, part2(&xs));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::grid;
+use crate::parse::Result;
+use crate::pt::Pt;
+use crate::solution::{self, Solution, Solved};
+use std::collections::{BinaryHeap, HashSet};
+
+pub type Grid = grid::Grid<u8>;
+
+fn is_low_point(g: &Grid, pt: Pt) -> bool {
+    g.nbrs4(pt).all(|nbr| g[nbr] > g[pt])
+}
+
+pub fn low_points(g: &Grid) -> Vec<Pt> {
+    g.pts().filter(|&pt| is_low_point(g, pt)).collect()
+}
+
+pub fn part1(g: &Grid, pts: &[Pt]) -> i32 {
+    pts.iter().map(|&pt| g[pt] as i32 + 1).sum()
+}
+
+fn explore(g: &Grid, pt: Pt, v: &mut HashSet<Pt>) -> i32 {
+    v.insert(pt);
+    for nbr in g.nbrs4(pt) {
+        if !v.contains(&nbr) && g[nbr] != 9 && g[nbr] > g[pt] {
+            explore(g, nbr, v);
+        }
+    }
+    v.len() as i32
+}
+
+pub fn part2(g: &Grid, low_points: &[Pt]) -> i32 {
+    let size = |&pt| explore(g, pt, &mut HashSet::new());
+    let mut sizes = low_points.iter().map(size).collect::<BinaryHeap<i32>>();
+    sizes.pop().unwrap() * sizes.pop().unwrap() * sizes.pop().unwrap()
+}
+
+pub fn parse(s: &str) -> Result<Grid> {
+    Grid::parse_digits(s)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = Grid;
+
+    fn parse(s: &str) -> solution::Result<Self::Input<'_>> {
+        parse(s)
+    }
+
+    fn part1(g: &Grid) -> Solved {
+        Ok(part1(g, &low_points(g)).into())
+    }
+
+    fn part2(g: &Grid) -> Solved {
+        Ok(part2(g, &low_points(g)).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = r"2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+    #[test]
+    fn test_part1() {
+        let g = parse(INPUT).unwrap();
+        assert_eq!(15, part1(&g, &low_points(&g)));
+    }
+
+    #[test]
+    fn test_part2() {
+        let g = parse(INPUT).unwrap();
+        assert_eq!(1134, part2(&g, &low_points(&g)));
+    }
+}
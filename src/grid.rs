@@ -0,0 +1,181 @@
+use crate::parse::{Result, Source};
+use crate::pt::{self, Pt};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl Grid<u8> {
+    pub fn parse_digits(s: &str) -> Result<Self> {
+        let digit = |ch: char| ch.to_digit(10).map(|x| x as u8);
+        Grid::parse_with(&Source::new(s), s, digit, "digit")
+    }
+}
+
+impl<T> Grid<T> {
+    // one row per line of s, which is part of src's text; cell names what
+    // a char should be, for errors
+    pub fn parse_with(
+        src: &Source,
+        s: &str,
+        f: impl Fn(char) -> Option<T>,
+        cell: &str,
+    ) -> Result<Self> {
+        let lines: Vec<&str> = s.lines().map(str::trim).collect();
+        let row = |line: &&str| {
+            let cell =
+                |(i, ch)| f(ch).ok_or_else(|| src.error(&line[i..], cell));
+            line.char_indices().map(cell).collect::<Result<Vec<T>>>()
+        };
+        let rows = lines.iter().map(row).collect::<Result<Vec<_>>>()?;
+        for (line, row) in lines.iter().zip(&rows) {
+            if row.len() != rows[0].len() {
+                let expected = format!("{} {}s", rows[0].len(), cell);
+                return Err(src.error(line, expected));
+            }
+        }
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == width), "ragged rows");
+        let cells = rows.into_iter().flatten().collect();
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn idx(&self, (r, c): Pt) -> Option<usize> {
+        let (r, c) = (usize::try_from(r).ok()?, usize::try_from(c).ok()?);
+        (r < self.height && c < self.width).then(|| r * self.width + c)
+    }
+
+    pub fn wrap(&self, (r, c): Pt) -> Pt {
+        (
+            r.rem_euclid(self.height as i32),
+            c.rem_euclid(self.width as i32),
+        )
+    }
+
+    pub fn contains(&self, pt: Pt) -> bool {
+        self.idx(pt).is_some()
+    }
+
+    pub fn get(&self, pt: Pt) -> Option<&T> {
+        self.idx(pt).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pt: Pt) -> Option<&mut T> {
+        self.idx(pt).map(|i| &mut self.cells[i])
+    }
+
+    pub fn pts(&self) -> impl Iterator<Item = Pt> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pt, &T)> {
+        self.pts().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn nbrs4(&self, pt: Pt) -> impl Iterator<Item = Pt> + '_ {
+        pt::nbrs4(pt).filter(|&nbr| self.contains(nbr))
+    }
+
+    pub fn nbrs8(&self, pt: Pt) -> impl Iterator<Item = Pt> + '_ {
+        pt::nbrs8(pt).filter(|&nbr| self.contains(nbr))
+    }
+}
+
+impl<T> Index<Pt> for Grid<T> {
+    type Output = T;
+    fn index(&self, pt: Pt) -> &T {
+        self.get(pt).expect("point out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pt> for Grid<T> {
+    fn index_mut(&mut self, pt: Pt) -> &mut T {
+        self.get_mut(pt).expect("point out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut g = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!(Some(&6), g.get((1, 2)));
+        assert_eq!(None, g.get((2, 0)));
+        assert_eq!(None, g.get((0, -1)));
+        g[(0, 0)] = 7;
+        assert_eq!(7, g[(0, 0)]);
+        assert_eq!(vec![(1, 0), (0, 1)], g.nbrs4((0, 0)).collect::<Vec<_>>());
+        assert_eq!(5, g.nbrs8((1, 1)).count());
+        assert_eq!((0, 0), g.wrap((2, 3)));
+        assert_eq!((1, 2), g.wrap((-1, -1)));
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let g = Grid::parse_digits("123\n456\n").unwrap();
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!(6, g[(1, 2)]);
+        let err = Grid::parse_digits("123\n45").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.col));
+        assert_eq!("3 digits", err.expected);
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let text = "header\n\n#.\n.x\n";
+        let (_, body) = text.split_once("\n\n").unwrap();
+        let bit = |ch| ['.', '#'].iter().position(|&c| c == ch);
+        let err = Grid::parse_with(&Source::new(text), body, bit, "bit");
+        let err = err.unwrap_err();
+        assert_eq!((4, 2), (err.line, err.col));
+        assert_eq!("bit", err.expected);
+        let s = "#.\n..";
+        let g = Grid::parse_with(&Source::new(s), s, bit, "bit");
+        assert_eq!(Some(&1), g.unwrap().get((0, 0)));
+    }
+}
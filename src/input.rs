@@ -0,0 +1,32 @@
+use crate::parse;
+use std::process::exit;
+
+pub fn path_from_args() -> String {
+    std::env::args().nth(1).unwrap_or_else(|| {
+        eprintln!("missing input path");
+        exit(2)
+    })
+}
+
+pub fn default_path(day: u32) -> String {
+    format!("inputs/day{}.txt", day)
+}
+
+pub fn answers_path(day: u32) -> String {
+    format!("answers/day{}.txt", day)
+}
+
+pub fn read_input() -> String {
+    let path = path_from_args();
+    std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        eprintln!("{}: {}", path, err);
+        exit(1)
+    })
+}
+
+pub fn or_exit<T>(result: parse::Result<T>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("{}:{}", path_from_args(), err);
+        exit(1)
+    })
+}
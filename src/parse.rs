@@ -0,0 +1,118 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}, ",
+            self.line, self.col, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found '{}'", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+// Locates errors by the position of a subslice within the whole input, so
+// parsers can keep slicing with split_once and friends.
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Source { text }
+    }
+
+    fn offset(&self, at: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let at = at.as_ptr() as usize;
+        if at < start || at > start + self.text.len() {
+            self.text.len()
+        } else {
+            at - start
+        }
+    }
+
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let offset = self.offset(at);
+        let before = &self.text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let col = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        let found = at.lines().next().unwrap_or("").to_string();
+        ParseError { line, col, expected: expected.into(), found }
+    }
+
+    pub fn eof(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        sep: &str,
+    ) -> Result<(&'a str, &'a str)> {
+        s.split_once(sep).ok_or_else(|| self.error(s, format!("'{}'", sep)))
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("'{}'", prefix)))
+    }
+
+    pub fn parse<T: FromStr>(&self, s: &'a str, expected: &str) -> Result<T> {
+        s.parse().map_err(|_| self.error(s, expected))
+    }
+
+    pub fn digits(&self, line: &'a str) -> Result<Vec<u8>> {
+        line.bytes()
+            .enumerate()
+            .map(|(i, b)| match b {
+                b'0'..=b'9' => Ok(b - b'0'),
+                _ => Err(self.error(&line[i..], "digit")),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_error() {
+        let text = "1,2\n3,x4\n";
+        let src = Source::new(text);
+        let line = text.lines().nth(1).unwrap();
+        let (_, y) = src.split_once(line, ",").unwrap();
+        let err = src.parse::<i32>(y, "integer").unwrap_err();
+        assert_eq!((2, 3), (err.line, err.col));
+        assert_eq!("2:3: expected integer, found 'x4'", err.to_string());
+        let err = src.split_once(line, " -> ").unwrap_err();
+        assert_eq!((2, 1, "3,x4"), (err.line, err.col, err.found.as_str()));
+        let err = src.eof("more lines");
+        assert_eq!((3, 1), (err.line, err.col));
+        assert_eq!(
+            "3:1: expected more lines, found end of line",
+            err.to_string()
+        );
+        let err = src.digits(line).unwrap_err();
+        assert_eq!((2, 2, "digit"), (err.line, err.col, err.expected.as_str()));
+    }
+}
@@ -0,0 +1,44 @@
+pub type Pt = (i32, i32);
+pub type Pt3 = (i64, i64, i64);
+
+static NBRS4: &[Pt] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+static NBRS8: &[Pt] = &[
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+pub fn nbrs4((r, c): Pt) -> impl Iterator<Item = Pt> {
+    NBRS4.iter().map(move |(dr, dc)| (r + dr, c + dc))
+}
+
+pub fn nbrs8((r, c): Pt) -> impl Iterator<Item = Pt> {
+    NBRS8.iter().map(move |(dr, dc)| (r + dr, c + dc))
+}
+
+pub fn manhattan((r1, c1): Pt, (r2, c2): Pt) -> i32 {
+    (r1 - r2).abs() + (c1 - c2).abs()
+}
+
+pub fn manhattan3((x1, y1, z1): Pt3, (x2, y2, z2): Pt3) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs() + (z1 - z2).abs()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nbrs() {
+        assert_eq!(4, nbrs4((0, 0)).count());
+        assert_eq!(8, nbrs8((0, 0)).count());
+        assert!(nbrs8((3, 4)).all(|pt| pt != (3, 4)));
+        assert!(nbrs4((3, 4)).all(|pt| manhattan(pt, (3, 4)) == 1));
+    }
+}
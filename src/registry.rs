@@ -0,0 +1,88 @@
+use crate::bench::{self, Timings};
+use crate::solution::{self, Solution, Solved};
+use crate::*;
+
+pub type Part = fn(&str) -> Solved;
+pub type Timer = fn(&str, u32) -> Result<Timings, solution::Error>;
+
+pub struct Puzzle {
+    pub day: u32,
+    pub part1: Part,
+    pub part2: Part,
+    pub time: Timer,
+}
+
+impl Puzzle {
+    const fn new<S: Solution>(day: u32) -> Self {
+        Puzzle {
+            day,
+            part1: part1::<S>,
+            part2: part2::<S>,
+            time: bench::time::<S>,
+        }
+    }
+
+    pub fn part(&self, part: u32) -> Option<Part> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+fn part1<S: Solution>(s: &str) -> Solved {
+    S::part1(&S::parse(s)?)
+}
+
+fn part2<S: Solution>(s: &str) -> Solved {
+    S::part2(&S::parse(s)?)
+}
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new::<day1::Day1>(1),
+    Puzzle::new::<day2::Day2>(2),
+    Puzzle::new::<day3::Day3>(3),
+    Puzzle::new::<day4::Day4>(4),
+    Puzzle::new::<day5::Day5>(5),
+    Puzzle::new::<day6::Day6>(6),
+    Puzzle::new::<day7::Day7>(7),
+    Puzzle::new::<day8::Day8>(8),
+    Puzzle::new::<day9::Day9>(9),
+    Puzzle::new::<day10::Day10>(10),
+    Puzzle::new::<day11::Day11>(11),
+    Puzzle::new::<day12::Day12>(12),
+    Puzzle::new::<day13::Day13>(13),
+    Puzzle::new::<day14::Day14>(14),
+    Puzzle::new::<day15::Day15>(15),
+    Puzzle::new::<day16::Day16>(16),
+    Puzzle::new::<day17::Day17>(17),
+    Puzzle::new::<day18::Day18>(18),
+    Puzzle::new::<day19::Day19>(19),
+    Puzzle::new::<day20::Day20>(20),
+    Puzzle::new::<day21::Day21>(21),
+    Puzzle::new::<day22::Day22>(22),
+    Puzzle::new::<day23::Day23>(23),
+    Puzzle::new::<day24::Day24>(24),
+    Puzzle::new::<day25::Day25>(25),
+];
+
+pub fn get(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::Answer;
+
+    #[test]
+    fn test_get() {
+        assert!(PUZZLES.windows(2).all(|w| w[0].day < w[1].day));
+        assert_eq!(15, get(15).unwrap().day);
+        assert!(get(26).is_none());
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        assert_eq!(Ok(Answer::Num(7)), (get(1).unwrap().part1)(input));
+        assert_eq!(Ok(Answer::Num(5)), (get(1).unwrap().part2)(input));
+    }
+}
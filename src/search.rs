@@ -0,0 +1,80 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// A* from start to the first node that satisfies is_goal, returning the
+// total cost and the nodes along the way, including both ends. nbrs gives
+// each neighbour with the cost of the step to it. heuristic must never
+// overestimate the remaining cost, and zero gives Dijkstra.
+pub fn astar<N, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut nbrs: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> i64,
+) -> Option<(i64, Vec<N>)>
+where
+    N: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    let mut dists = HashMap::from([(start.clone(), 0)]);
+    let mut prev: HashMap<N, N> = HashMap::new();
+    let mut q = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+    while let Some(Reverse((_, dist, node))) = q.pop() {
+        if dist > dists[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(node) = prev.get(path.last().unwrap()) {
+                path.push(node.clone());
+            }
+            path.reverse();
+            return Some((dist, path));
+        }
+        for (nbr, cost) in nbrs(&node) {
+            let nbr_dist = dist + cost;
+            if dists.get(&nbr).is_none_or(|&best| nbr_dist < best) {
+                dists.insert(nbr.clone(), nbr_dist);
+                prev.insert(nbr.clone(), node.clone());
+                q.push(Reverse((nbr_dist + heuristic(&nbr), nbr_dist, nbr)));
+            }
+        }
+    }
+    None
+}
+
+pub fn dijkstra<N, I>(
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+    nbrs: impl FnMut(&N) -> I,
+) -> Option<(i64, Vec<N>)>
+where
+    N: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    astar(start, is_goal, nbrs, |_| 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        // a line 0..10 with a shortcut from 2 to 8 that costs 3
+        let nbrs = |&n: &i64| {
+            let mut nbrs = vec![n - 1, n + 1];
+            if n == 2 {
+                nbrs.push(8);
+            }
+            let nbrs = nbrs.into_iter().filter(|n| (0..10).contains(n));
+            nbrs.map(move |m| (m, if (m - n).abs() == 1 { 1 } else { 3 }))
+        };
+        let (dist, path) = dijkstra(0, |&n| n == 9, nbrs).unwrap();
+        assert_eq!(6, dist);
+        assert_eq!(vec![0, 1, 2, 8, 9], path);
+        let h = |&n: &i64| (9 - n).min(1);
+        assert_eq!(Some((6, path)), astar(0, |&n| n == 9, nbrs, h));
+        assert_eq!(None, dijkstra(0, |&n| n == 10, nbrs));
+    }
+}
@@ -0,0 +1,103 @@
+pub use crate::parse::{ParseError, Result};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Num(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Num(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Num(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Num(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Num(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Num(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Num(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+// why a part has no answer: the input didn't parse, or it did but the part
+// can't be solved for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Solve(why) => write!(f, "{}", why),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+pub type Solved = std::result::Result<Answer, Error>;
+
+pub trait Solution {
+    type Input<'a>;
+    fn parse(s: &str) -> Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>) -> Solved;
+    fn part2(input: &Self::Input<'_>) -> Solved;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wide_answers() {
+        assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+        assert_eq!(Answer::Num(i128::MAX), Answer::from(i128::MAX as u128));
+        let n = u128::MAX;
+        assert_eq!(Answer::Text(n.to_string()), Answer::from(n));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::registry::Puzzle;
+use std::fmt;
+use std::panic;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail(why) => write!(f, "FAIL: {}", why),
+            Outcome::Missing => write!(f, "missing answer"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub part: u32,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+// answers/dayN.txt holds the part 1 answer on the first line and the part 2
+// answer on the rest, so that multi-line answers (day 13) fit too.
+pub fn expected(answers: &str, part: u32) -> Option<&str> {
+    let (fst, snd) = answers.split_once('\n').unwrap_or((answers, ""));
+    let answer = match part {
+        1 => fst,
+        2 => snd,
+        _ => return None,
+    };
+    Some(answer.trim_end()).filter(|answer| !answer.is_empty())
+}
+
+pub fn check(
+    puzzle: &Puzzle,
+    part: u32,
+    input: &str,
+    answers: Option<&str>,
+) -> Check {
+    let solve = puzzle.part(part).expect("bad part");
+    let start = Instant::now();
+    // a panic fails just this part, so the other days still get checked
+    let result = panic::catch_unwind(|| solve(input));
+    let elapsed = start.elapsed();
+    let want = answers.and_then(|answers| expected(answers, part));
+    let outcome = match (result, want) {
+        (Err(_), _) => Outcome::Fail("panicked".to_string()),
+        (Ok(Err(err)), _) => Outcome::Fail(err.to_string()),
+        (Ok(Ok(_)), None) => Outcome::Missing,
+        (Ok(Ok(got)), Some(want)) if got.to_string().trim_end() == want => {
+            Outcome::Pass
+        }
+        (Ok(Ok(got)), Some(want)) => {
+            Outcome::Fail(format!("expected {}, found {}", want, got))
+        }
+    };
+    Check { part, outcome, elapsed }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry;
+    use crate::solution::Answer;
+
+    static INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+    #[test]
+    fn test_expected() {
+        assert_eq!(Some("7"), expected("7\n5\n", 1));
+        assert_eq!(Some("5"), expected("7\n5\n", 2));
+        assert_eq!(Some("#..\n.#."), expected("17\n#..\n.#.\n", 2));
+        assert_eq!(None, expected("7\n", 2));
+        assert_eq!(None, expected("", 1));
+    }
+
+    #[test]
+    fn test_check() {
+        let day1 = registry::get(1).unwrap();
+        assert_eq!(Outcome::Pass, check(day1, 1, INPUT, Some("7\n5")).outcome);
+        assert_eq!(
+            Outcome::Fail("expected 6, found 5".to_string()),
+            check(day1, 2, INPUT, Some("7\n6")).outcome
+        );
+        assert_eq!(Outcome::Missing, check(day1, 2, INPUT, Some("7")).outcome);
+        assert_eq!(Outcome::Missing, check(day1, 1, INPUT, None).outcome);
+        let err = check(day1, 1, "x", None).outcome;
+        assert!(matches!(err, Outcome::Fail(_)));
+        let day16 = registry::get(16).unwrap();
+        let why = "operator without operands at bit 0".to_string();
+        assert_eq!(Outcome::Fail(why), check(day16, 2, "0C0000", None).outcome);
+    }
+
+    #[test]
+    fn test_check_panic() {
+        let puzzle = Puzzle {
+            day: 0,
+            part1: |_| panic!("no answer"),
+            part2: |_| Ok(Answer::Num(1)),
+            time: |_, _| panic!("no timings"),
+        };
+        let why = "panicked".to_string();
+        assert_eq!(
+            Outcome::Fail(why),
+            check(&puzzle, 1, "", Some("1\n1")).outcome
+        );
+        assert_eq!(Outcome::Pass, check(&puzzle, 2, "", Some("1\n1")).outcome);
+    }
+}